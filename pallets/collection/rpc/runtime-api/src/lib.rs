@@ -1,8 +1,26 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 
+use codec::Codec;
+pub use pallet_collection::{CollectionId, NFTId};
+use sp_runtime::sp_std::vec::Vec;
+
 sp_api::decl_runtime_apis! {
-	pub trait KittyApi
+	#[api_version(3)]
+	pub trait KittyApi<AccountId, Balance>
+	where
+		AccountId: Codec,
+		Balance: Codec,
 	{
 		fn get_launchpad_collections() -> u32;
+
+		/// The royalty recipient and amount owed on a sale of an NFT for `sale_price`.
+		fn royalty_info(
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			sale_price: Balance,
+		) -> Option<(AccountId, Balance)>;
+
+		/// The collection whose name normalizes to `slug`.
+		fn collection_by_slug(slug: Vec<u8>) -> Option<CollectionId>;
 	}
 }
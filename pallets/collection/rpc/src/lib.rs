@@ -1,15 +1,33 @@
+use codec::Codec;
 use jsonrpc_core::{Error as RpcError, ErrorCode, Result};
 use jsonrpc_derive::rpc;
 pub use pallet_collection_rpc_runtime_api::KittyApi as KittyRuntimeApi;
+use pallet_collection_rpc_runtime_api::{CollectionId, NFTId};
 use sp_api::ProvideRuntimeApi;
 use sp_blockchain::HeaderBackend;
 use sp_runtime::{generic::BlockId, traits::Block as BlockT};
 use std::sync::Arc;
 
 #[rpc]
-pub trait KittyApi<BlockHash> {
+pub trait KittyApi<BlockHash, AccountId, Balance> {
 	#[rpc(name = "get_launchpad_collections")]
 	fn get_launchpad_collections(&self, at: Option<BlockHash>) -> Result<u32>;
+
+	#[rpc(name = "royalty_info")]
+	fn royalty_info(
+		&self,
+		collection_id: CollectionId,
+		nft_id: NFTId,
+		sale_price: Balance,
+		at: Option<BlockHash>,
+	) -> Result<Option<(AccountId, Balance)>>;
+
+	#[rpc(name = "collection_by_slug")]
+	fn collection_by_slug(
+		&self,
+		slug: String,
+		at: Option<BlockHash>,
+	) -> Result<Option<CollectionId>>;
 }
 
 /// A struct that implements the [`KittyApi`].
@@ -42,11 +60,14 @@ impl From<Error> for i64 {
 	}
 }
 
-impl<C, Block> KittyApi<<Block as BlockT>::Hash> for Kitty<C, Block>
+impl<C, Block, AccountId, Balance> KittyApi<<Block as BlockT>::Hash, AccountId, Balance>
+	for Kitty<C, Block>
 where
 	Block: BlockT,
 	C: 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
-	C::Api: KittyRuntimeApi<Block>,
+	C::Api: KittyRuntimeApi<Block, AccountId, Balance>,
+	AccountId: Codec,
+	Balance: Codec,
 {
 	fn get_launchpad_collections(&self, at: Option<<Block as BlockT>::Hash>) -> Result<u32> {
 		let api = self.client.runtime_api();
@@ -62,4 +83,38 @@ where
 			data: Some(e.to_string().into()),
 		})
 	}
+	fn royalty_info(
+		&self,
+		collection_id: CollectionId,
+		nft_id: NFTId,
+		sale_price: Balance,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(AccountId, Balance)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.royalty_info(&at, collection_id, nft_id, sale_price).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query royalty info.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
+	fn collection_by_slug(
+		&self,
+		slug: String,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<CollectionId>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(||
+			// If the block hash is not supplied assume the best block.
+			self.client.info().best_hash));
+
+		api.collection_by_slug(&at, slug.into_bytes()).map_err(|e| RpcError {
+			code: ErrorCode::ServerError(Error::RuntimeError.into()),
+			message: "Unable to query collection by slug.".into(),
+			data: Some(e.to_string().into()),
+		})
+	}
 }
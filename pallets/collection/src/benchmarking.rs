@@ -4,16 +4,115 @@ use super::*;
 
 #[allow(unused)]
 use crate::Pallet as Template;
-use frame_benchmarking::{benchmarks, whitelisted_caller};
+use frame_benchmarking::{account, benchmarks, whitelisted_caller};
+use frame_support::sp_runtime::traits::{Bounded, Hash};
+use frame_support::sp_std::vec::Vec;
+use frame_support::traits::{tokens::nonfungibles::Mutate, Currency, EnsureOrigin, Get};
 use frame_system::RawOrigin;
 
+/// Register a collection of `number_of_items` items priced at the existential deposit.
+fn register<T: Config>(
+	owner: T::AccountId,
+	number_of_items: u32,
+) -> Result<CollectionId, &'static str> {
+	T::Currency::make_free_balance_be(&owner, BalanceOf::<T>::max_value() / 2u32.into());
+	Pallet::<T>::register_collection(
+		RawOrigin::Signed(owner).into(),
+		CollectionConfig {
+			name: b"Collection".to_vec(),
+			description: b"Description".to_vec(),
+			base_uri: b"ipfs://base/".to_vec(),
+			number_of_items,
+			mint_fee: T::Currency::minimum_balance(),
+			goal: 0u32.into(),
+			fund_end: T::BlockNumber::max_value(),
+			redeemable: false,
+			royalty: None,
+			sale_mode: SaleMode::FixedPrice,
+			max_per_account: None,
+			payment_asset: PaymentAsset::Native,
+			soulbound: false,
+			milestones: Default::default(),
+		},
+	)?;
+	Ok(Pallet::<T>::fund_count() - 1)
+}
+
+fn approve<T: Config>(collection_id: CollectionId) -> Result<(), &'static str> {
+	Pallet::<T>::approve_collection(
+		T::ApproveOrigin::successful_origin(),
+		collection_id,
+		0,
+		u64::MAX,
+		SaleWindowUnit::BlockNumber,
+	)?;
+	Ok(())
+}
+
 benchmarks! {
-	do_something {
-		let s in 0 .. 100;
+	mint {
+		let q in 1 .. T::MaxMintPerTx::get();
+		let collection_id = register::<T>(account("owner", 0, 0), q)?;
+		approve::<T>(collection_id)?;
+		let caller: T::AccountId = whitelisted_caller();
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+	}: _(RawOrigin::Signed(caller.clone()), collection_id, q)
+	verify {
+		assert_eq!(Pallet::<T>::get_collection_owner_nft(collection_id, &caller).len() as u32, q);
+	}
+
+	mint_allowlisted {
+		let q in 1 .. T::MaxMintPerTx::get();
+		let p in 0 .. 32;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = register::<T>(owner.clone(), q)?;
 		let caller: T::AccountId = whitelisted_caller();
-	}: _(RawOrigin::Signed(caller), s)
+		T::Currency::make_free_balance_be(&caller, BalanceOf::<T>::max_value() / 2u32.into());
+
+		let proof: Vec<T::Hash> = (0..p).map(|i| T::Hashing::hash_of(&i)).collect();
+		let root = Pallet::<T>::merkle_root_of(T::Hashing::hash_of(&(&caller, q)), &proof);
+		Pallet::<T>::set_presale(
+			RawOrigin::Signed(owner).into(),
+			collection_id,
+			root,
+			T::Currency::minimum_balance(),
+		)?;
+		approve::<T>(collection_id)?;
+	}: _(RawOrigin::Signed(caller.clone()), collection_id, proof, q, q)
+	verify {
+		assert_eq!(Pallet::<T>::allowlist_claimed(collection_id, &caller), q);
+	}
+
+	cancel_collection {
+		let n in 0 .. 100;
+		let m in 0 .. 100;
+		let a in 0 .. 100;
+		let owner: T::AccountId = account("owner", 0, 0);
+		let collection_id = register::<T>(owner.clone(), 100)?;
+		for i in 0 .. n {
+			Pallet::<T>::mint_into(&collection_id, &i, &account("holder", i, 0))?;
+		}
+		for i in 0 .. m {
+			Pallet::<T>::set_item_metadata(
+				RawOrigin::Signed(owner.clone()).into(),
+				collection_id,
+				i,
+				b"ipfs://item".to_vec(),
+			)?;
+		}
+		for i in 0 .. a {
+			Pallet::<T>::set_attribute(
+				RawOrigin::Signed(owner.clone()).into(),
+				collection_id,
+				None,
+				i.to_le_bytes().to_vec(),
+				b"value".to_vec(),
+			)?;
+		}
+		let witness = Pallet::<T>::cancel_witness(collection_id).ok_or("no collection")?;
+	}: _(RawOrigin::Signed(owner), collection_id, witness)
 	verify {
-		assert_eq!(Something::<T>::get(), Some(s));
+		assert!(Pallet::<T>::get_collections(collection_id).is_none());
 	}
 
 	impl_benchmark_test_suite!(Template, crate::mock::new_test_ext(), crate::mock::Test);
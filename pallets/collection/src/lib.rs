@@ -14,9 +14,13 @@ mod tests;
 #[cfg(feature = "runtime-benchmarks")]
 mod benchmarking;
 
+pub mod migrations;
+pub mod weights;
+
 #[frame_support::pallet]
 pub mod pallet {
 
+	pub use crate::weights::WeightInfo;
 	use frame_support::inherent::Vec;
 	use frame_support::pallet_prelude::*;
 	use frame_support::traits::Currency;
@@ -24,36 +28,67 @@ pub mod pallet {
 	use scale_info::{prelude::format, TypeInfo};
 
 	use frame_support::{
-		sp_runtime::traits::{AccountIdConversion, Zero},
-		traits::{ExistenceRequirement, Randomness, ReservableCurrency, WithdrawReasons},
-		PalletId,
+		sp_runtime::traits::{
+			AccountIdConversion, CheckedSub, Hash, Saturating, TrailingZeroInput,
+			UniqueSaturatedInto, Zero,
+		},
+		sp_std::boxed::Box,
+		storage::{child, with_transaction, TransactionOutcome},
+		traits::{
+			tokens::{fungibles, nonfungibles},
+			EnsureOrigin, ExistenceRequirement, OnUnbalanced, Randomness,
+			ReservableCurrency, Time, WithdrawReasons,
+		},
+		sp_runtime::{Perbill, Perquintill},
+		transactional, PalletId,
 	};
 
 	pub type CollectionId = u32;
-	pub type NFTId = u16;
+	pub type NFTId = u32;
 
 	type AccountOf<T> = <T as frame_system::Config>::AccountId;
-	type BalanceOf<T> =
+	pub type BalanceOf<T> =
 		<<T as Config>::Currency as Currency<<T as frame_system::Config>::AccountId>>::Balance;
+	type NegativeImbalanceOf<T> = <<T as Config>::Currency as Currency<
+		<T as frame_system::Config>::AccountId,
+	>>::NegativeImbalance;
 	#[cfg(feature = "std")]
 	use frame_support::serde::{Deserialize, Serialize};
 
 	// Funding
 	pub type FundIndex = u32;
 	const PALLET_ID: PalletId = PalletId(*b"ex/cfund");
-	type FundInfoOf<T> =
-		FundInfo<AccountOf<T>, BalanceOf<T>, <T as frame_system::Config>::BlockNumber>;
+	pub type MilestoneOf<T> = Milestone<
+		BalanceOf<T>,
+		<T as frame_system::Config>::Hash,
+		<T as frame_system::Config>::BlockNumber,
+	>;
+	pub type CollectionConfigOf<T> = CollectionConfig<
+		AccountOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+		<T as frame_system::Config>::Hash,
+	>;
+	type FundInfoOf<T> = FundInfo<
+		AccountOf<T>,
+		BalanceOf<T>,
+		<T as frame_system::Config>::BlockNumber,
+		<T as Config>::AssetId,
+	>;
 
-	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
 	#[scale_info(skip_type_params(T))]
 	#[codec(mel_bound())]
 	pub struct CollectionInfo<T: Config> {
 		pub id: CollectionId,
 		pub owner: AccountOf<T>,
-		pub name: Vec<u8>,
-		pub description: Vec<u8>,
-		pub number_of_items: u16,
-		pub number_of_minted: u16,
+		pub name: BoundedVec<u8, T::NameLimit>,
+		pub description: BoundedVec<u8, T::DescriptionLimit>,
+		/// Base URI or CID of the collection metadata. Item URIs are derived from it.
+		pub base_uri: BoundedVec<u8, T::UriLimit>,
+		pub number_of_items: u32,
+		pub number_of_minted: u32,
 		/// The metadata of this metaverse
 		// pub metadata: MetaverseMetadata,
 		/// The currency use in this metaverse
@@ -62,16 +97,144 @@ pub mod pallet {
 		pub is_frozen: bool,
 		pub project_status: ProjectStatus,
 		pub mint_fee: BalanceOf<T>,
-		pub start_date: Option<u32>,
-		pub end_date: Option<u32>,
+		pub start_date: Option<u64>,
+		pub end_date: Option<u64>,
+		/// Whether `start_date` and `end_date` are block numbers or timestamp moments.
+		pub sale_window_unit: SaleWindowUnit,
+		/// Whether burning an item pays out its share of the collection fund.
+		pub redeemable: bool,
+		/// Number of burned items. `number_of_minted` only counts the outstanding ones.
+		pub number_of_burned: u32,
+		/// Royalty charged on secondary sales of the collection items.
+		pub royalty: Option<RoyaltyInfo<AccountOf<T>>>,
+		/// How the collection items are priced during the sale window.
+		pub sale_mode: SaleMode<BalanceOf<T>>,
+		/// Whether only allowlisted accounts or everyone can mint.
+		pub sale_phase: SalePhase,
+		/// The allowlist and price of the presale.
+		pub presale: Option<PresaleInfo<T::Hash, BalanceOf<T>>>,
+		/// The maximum number of items a single account can mint, if any.
+		pub max_per_account: Option<u32>,
+		/// Whether the collection items can never be transferred.
+		pub soulbound: bool,
+		/// Manages the sale: presale allowlist and sale phases.
+		pub issuer: AccountOf<T>,
+		/// Manages the metadata, royalty and reveal, and thaws the collection and its items.
+		pub admin: AccountOf<T>,
+		/// Freezes the collection and its items.
+		pub freezer: AccountOf<T>,
+		/// The normalized name used in URLs, unique across collections. Empty for collections
+		/// created without a name and for rejected collections, whose name is freed.
+		pub slug: BoundedVec<u8, T::NameLimit>,
 	}
 
+	/// The collection registered by `register_collection` and the fund raising its goal.
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo)]
-	#[codec(mel_bound())]
-	pub struct NFT {
-		pub id: u16,
+	pub struct CollectionConfig<AccountId, Balance, BlockNumber, AssetId, Hash> {
 		pub name: Vec<u8>,
-		pub image_url: Vec<u8>,
+		pub description: Vec<u8>,
+		/// Base URI or CID of the collection metadata. Item URIs are derived from it.
+		pub base_uri: Vec<u8>,
+		pub number_of_items: u32,
+		pub mint_fee: Balance,
+		/// The amount that must be raised by `fund_end` for the fund to succeed.
+		pub goal: Balance,
+		/// Block number after which funding must have succeeded.
+		pub fund_end: BlockNumber,
+		/// Whether burning an item pays out its share of the collection fund.
+		pub redeemable: bool,
+		/// Royalty charged on secondary sales of the collection items.
+		pub royalty: Option<RoyaltyInfo<AccountId>>,
+		/// How the collection items are priced during the sale window.
+		pub sale_mode: SaleMode<Balance>,
+		/// The maximum number of items a single account can mint, if any.
+		pub max_per_account: Option<u32>,
+		/// The asset mint fees and contributions are paid in.
+		pub payment_asset: PaymentAsset<AssetId>,
+		/// Whether the collection items can never be transferred.
+		pub soulbound: bool,
+		/// Releases of the raised funds, adding up to `goal`. The whole fund is dispensed at
+		/// once if there are none.
+		pub milestones: Vec<Milestone<Balance, Hash, BlockNumber>>,
+	}
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum SalePhase {
+		/// Only accounts of the presale allowlist can mint, with `mint_allowlisted`.
+		Allowlist,
+		/// Everyone can mint.
+		Public,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct PresaleInfo<Hash, Balance> {
+		/// Root of a Merkle tree whose leaves are the hashes of `(account, quota)` pairs.
+		pub merkle_root: Hash,
+		/// The price of an item minted in the presale.
+		pub price: Balance,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub enum SaleMode<Balance> {
+		/// Every item is minted for `mint_fee`.
+		FixedPrice,
+		/// The price decays by `decay` per block since the sale window opened, from
+		/// `start_price` down to `floor_price`.
+		DutchAuction { start_price: Balance, floor_price: Balance, decay: Balance },
+		/// The single item of the collection goes to the highest bid of at least `reserve_price`
+		/// when the sale window closes.
+		EnglishAuction { reserve_price: Balance },
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Milestone<Balance, Hash, BlockNumber> {
+		/// The share of the fund released to the creator once the milestone is approved.
+		pub amount: Balance,
+		/// Hash of the off-chain description of the milestone deliverables.
+		pub description: Hash,
+		/// The milestone fails if it is not released by this block.
+		pub deadline: BlockNumber,
+	}
+
+	#[derive(Clone, Encode, Decode, Default, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct MilestoneProgress<Balance> {
+		/// Index of the milestone being voted on.
+		pub current: u32,
+		/// Number of items whose holders approved the current milestone.
+		pub ayes: u32,
+		/// Number of items whose holders rejected the current milestone.
+		pub nays: u32,
+		/// Once the current milestone failed, the share of the unreleased funds refunded per item.
+		pub refund_per_item: Option<Balance>,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct FractionInfo<AssetId, Balance> {
+		/// The `T::Assets` token the shares of the item are issued in.
+		pub asset_id: AssetId,
+		/// The number of shares issued when the item was fractionalized.
+		pub supply: Balance,
+		/// The price anyone can buy the whole item out for, if any.
+		pub reserve_price: Option<Balance>,
+		/// Once the item was bought out, the part of the price not yet claimed by shareholders.
+		pub proceeds: Option<Balance>,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct RoyaltyInfo<AccountId> {
+		/// The account receiving the royalty.
+		pub recipient: AccountId,
+		/// The share of the sale price paid as royalty.
+		pub rate: Perbill,
+	}
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[scale_info(skip_type_params(T))]
+	#[codec(mel_bound())]
+	pub struct NFT<T: Config> {
+		pub id: NFTId,
+		pub name: BoundedVec<u8, T::NameLimit>,
+		pub image_url: BoundedVec<u8, T::UriLimit>,
 	}
 
 	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
@@ -79,16 +242,31 @@ pub mod pallet {
 	pub enum ProjectStatus {
 		Draft,
 		Approved,
+		Rejected,
+	}
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum SaleWindowUnit {
+		/// The sale window is expressed in block numbers.
+		BlockNumber,
+		/// The sale window is expressed in `pallet_timestamp` moments.
+		Moment,
 	}
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_collections)]
 	pub type Collections<T: Config> = StorageMap<_, Twox64Concat, CollectionId, CollectionInfo<T>>;
 
+	/// The collection registered under a slug, keyed by the hash of the slug. Keeps collection names
+	/// unique.
+	#[pallet::storage]
+	pub type CollectionByName<T: Config> = StorageMap<_, Identity, T::Hash, CollectionId>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn get_nfts)]
 	pub type NFTs<T: Config> =
-		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, NFT, ValueQuery>;
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, NFT<T>, ValueQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn get_collection_owner_nft)]
@@ -99,23 +277,67 @@ pub mod pallet {
 	#[pallet::getter(fn owner)]
 	/// Keeps track of what accounts own what NFT.
 	pub(super) type NFTOwned<T: Config> =
-		StorageMap<_, Twox64Concat, NFTId, T::AccountId, OptionQuery>;
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, T::AccountId, OptionQuery>;
 
 	#[pallet::storage]
 	#[pallet::getter(fn nfts_map)]
-	pub(super) type NFTMap<T: Config> = StorageMap<_, Twox64Concat, NFTId, NFT>;
+	pub(super) type NFTMap<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, NFT<T>>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn item_metadata)]
+	/// Metadata URIs uploaded for individual items before the collection is approved, with the
+	/// account and amount of the deposit reserved for them. The URI is moved to the item when it
+	/// is minted. URIs uploaded before deposits were taken hold no deposit.
+	pub(super) type ItemMetadata<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Twox64Concat,
+		NFTId,
+		(BoundedVec<u8, T::UriLimit>, Option<(T::AccountId, BalanceOf<T>)>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn approvals)]
+	/// The account allowed to transfer an NFT on behalf of its owner.
+	pub(super) type NFTApprovals<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, T::AccountId, OptionQuery>;
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+	pub enum PaymentAsset<AssetId> {
+		/// Mint fees and contributions are paid in `T::Currency`.
+		Native,
+		/// Mint fees and contributions are paid in a `T::Assets` token.
+		Asset(AssetId),
+	}
+
+	impl<AssetId> Default for PaymentAsset<AssetId> {
+		fn default() -> Self {
+			PaymentAsset::Native
+		}
+	}
 
 	#[derive(Encode, Decode, Default, PartialEq, Eq, TypeInfo, MaxEncodedLen)]
 	#[cfg_attr(feature = "std", derive(serde::Serialize, serde::Deserialize))]
-	pub struct FundInfo<AccountId, Balance, BlockNumber> {
+	pub struct FundInfo<AccountId, Balance, BlockNumber, AssetId> {
 		/// The account that will recieve the funds if the campaign is successful.
-		beneficiary: AccountId,
-		/// The amount of deposit placed.
-		deposit: Balance,
+		pub beneficiary: AccountId,
+		/// The `SubmissionDeposit` reserved from the beneficiary until the collection is approved,
+		/// rejected or cancelled, or the fund is dissolved or dispensed.
+		pub deposit: Balance,
 		/// The total amount raised.
-		raised: Balance,
+		pub raised: Balance,
 		/// Block number after which funding must have succeeded.
-		end: BlockNumber,
+		pub end: BlockNumber,
+		/// The amount that must be raised by `end` for the fund to succeed.
+		pub goal: Balance,
+		/// The asset contributions are paid in. `deposit` is always paid in `T::Currency`.
+		pub asset: PaymentAsset<AssetId>,
+		/// The total protocol fee taken from contributions. It is not part of `raised`.
+		pub fees: Balance,
 	}
 
 	#[pallet::storage]
@@ -124,6 +346,175 @@ pub mod pallet {
 	pub(super) type Funds<T: Config> =
 		StorageMap<_, Blake2_128Concat, FundIndex, FundInfoOf<T>, OptionQuery>;
 
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct RevealInfo<Hash> {
+		/// Hash of the SCALE encoded, ordered list of item metadata URIs, committed before approval.
+		pub provenance: Hash,
+		/// Offset into the committed list, drawn from on-chain randomness after the sale ended.
+		pub offset: Option<u32>,
+		/// Whether the final metadata has been assigned to the minted items.
+		pub revealed: bool,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn reveals)]
+	/// Delayed reveal commitments of collections whose items are minted as placeholders.
+	pub(super) type Reveals<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, RevealInfo<T::Hash>, OptionQuery>;
+
+	#[derive(Clone, Encode, Decode, PartialEq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct Listing<AccountId, Balance> {
+		/// The owner who listed the NFT.
+		pub seller: AccountId,
+		/// The fixed price asked for the NFT, royalty included.
+		pub price: Balance,
+	}
+
+	#[pallet::storage]
+	#[pallet::getter(fn listings)]
+	/// NFTs listed for sale at a fixed price.
+	pub(super) type Listings<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Twox64Concat,
+		NFTId,
+		Listing<T::AccountId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sale_opened_at)]
+	/// The block in which the sale window of a collection opened. Dutch auctions decay from it.
+	pub(super) type SaleOpenedAt<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, T::BlockNumber, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn highest_bid)]
+	/// The highest bid of an English auction. The bid amount is reserved from the bidder.
+	pub(super) type Bids<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, (T::AccountId, BalanceOf<T>), OptionQuery>;
+
+	#[pallet::storage]
+	/// English auctions whose sale window closed in this block, settled in `on_finalize`.
+	pub(super) type AuctionsToSettle<T: Config> =
+		StorageValue<_, BoundedVec<CollectionId, T::MaxActiveSaleWindows>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn allowlist_claimed)]
+	/// Number of items minted by an account in the presale of a collection.
+	pub(super) type AllowlistClaimed<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn minted_per_account)]
+	/// Number of items minted by an account from a collection, checked against `max_per_account`.
+	pub(super) type MintedPerAccount<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Blake2_128Concat,
+		T::AccountId,
+		u32,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn is_item_frozen)]
+	/// Items that can not be transferred until they are thawed.
+	pub(super) type FrozenItems<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	/// Key/value attributes of collections (`None` item) and their items, with the account and
+	/// amount of the deposit reserved for them. Attributes set through `nonfungibles::Mutate`
+	/// hold no deposit.
+	pub(super) type Attributes<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, CollectionId>,
+			NMapKey<Twox64Concat, Option<NFTId>>,
+			NMapKey<Blake2_128Concat, BoundedVec<u8, T::KeyLimit>>,
+		),
+		(BoundedVec<u8, T::ValueLimit>, Option<(T::AccountId, BalanceOf<T>)>),
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn milestones)]
+	/// The milestones the fund of a collection is released in, in order.
+	pub(super) type Milestones<T: Config> = StorageMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		BoundedVec<MilestoneOf<T>, T::MaxMilestones>,
+		ValueQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn milestone_progress)]
+	/// The milestone of a collection being voted on and its tally.
+	pub(super) type CurrentMilestone<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, MilestoneProgress<BalanceOf<T>>, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn milestone_vote)]
+	/// The vote cast with an item on a milestone of its collection.
+	pub(super) type MilestoneVotes<T: Config> = StorageNMap<
+		_,
+		(
+			NMapKey<Twox64Concat, CollectionId>,
+			NMapKey<Twox64Concat, u32>,
+			NMapKey<Twox64Concat, NFTId>,
+		),
+		bool,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn milestone_refunded)]
+	/// Items whose share of the unreleased funds of a failed milestone was refunded.
+	pub(super) type MilestoneRefunded<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, CollectionId, Twox64Concat, NFTId, bool, ValueQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn fractions)]
+	/// Items locked in their fraction account and split into `T::Assets` shares.
+	pub(super) type Fractions<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		CollectionId,
+		Twox64Concat,
+		NFTId,
+		FractionInfo<T::AssetId, BalanceOf<T>>,
+		OptionQuery,
+	>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn ownership_acceptance)]
+	/// The account a collection owner offered the ownership of the collection to.
+	pub(super) type OwnershipAcceptance<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, T::AccountId, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn rejection_reason)]
+	/// Why a collection was rejected by the `ApproveOrigin`.
+	pub(super) type RejectionReasons<T: Config> =
+		StorageMap<_, Twox64Concat, CollectionId, BoundedVec<u8, T::DescriptionLimit>, OptionQuery>;
+
+	#[pallet::storage]
+	#[pallet::getter(fn sale_windows)]
+	/// Approved collections whose sale window has not closed yet, and whether it has opened.
+	pub(super) type SaleWindows<T: Config> =
+		StorageValue<_, BoundedVec<(CollectionId, bool), T::MaxActiveSaleWindows>, ValueQuery>;
+
 	#[pallet::storage]
 	#[pallet::getter(fn fund_count)]
 	/// The total number of funds that have so far been allocated.
@@ -142,38 +533,139 @@ pub mod pallet {
 		// type ReservableCurrency: ReservableCurrency<Self::AccountId>;
 		type CollectionRandomness: Randomness<Self::Hash, Self::BlockNumber>;
 
-		// The amount to be held on deposit by the owner of a crowdfund.
+		// The amount to be held on deposit by the owner of a crowdfund. The existential deposit
+		// of it endows the fund account and is not returned.
 		type SubmissionDeposit: Get<BalanceOf<Self>>;
 
+		/// The origin allowed to approve or reject collections, e.g. root, a council or a passed
+		/// DAO proposal.
+		type ApproveOrigin: EnsureOrigin<Self::Origin>;
+
+		/// Handler for the `SubmissionDeposit` slashed from rejected collections.
+		type Slash: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
 		#[pallet::constant]
 		type MaxNFTOwned: Get<u32>;
+
+		/// Time provider used for sale windows expressed in moments.
+		type Timestamp: Time;
+
+		/// The maximum number of collections with a pending or running sale window.
+		#[pallet::constant]
+		type MaxActiveSaleWindows: Get<u32>;
+
+		/// The maximum length of a metadata base URI or item URI.
+		#[pallet::constant]
+		type UriLimit: Get<u32>;
+
+		/// The maximum length of a collection or NFT name.
+		#[pallet::constant]
+		type NameLimit: Get<u32>;
+
+		/// The maximum length of a collection description.
+		#[pallet::constant]
+		type DescriptionLimit: Get<u32>;
+
+		/// Identifier of a `T::Assets` token.
+		type AssetId: Member + Parameter + Copy + MaxEncodedLen + TypeInfo;
+
+		/// Fungible tokens collections can be paid in instead of `T::Currency`. Shares of
+		/// fractionalized items are created as such tokens and destroyed once all are burned.
+		type Assets: fungibles::Transfer<
+				Self::AccountId,
+				AssetId = Self::AssetId,
+				Balance = BalanceOf<Self>,
+			> + fungibles::Create<Self::AccountId>
+			+ fungibles::Mutate<Self::AccountId>
+			+ fungibles::Destroy<Self::AccountId>
+			+ fungibles::Balanced<Self::AccountId>;
+
+		/// The maximum number of items minted in a single `mint` call.
+		#[pallet::constant]
+		type MaxMintPerTx: Get<u32>;
+
+		/// The maximum length of an attribute key.
+		#[pallet::constant]
+		type KeyLimit: Get<u32>;
+
+		/// The maximum length of an attribute value.
+		#[pallet::constant]
+		type ValueLimit: Get<u32>;
+
+		/// The share of every contribution paid as protocol fee.
+		#[pallet::constant]
+		type ProtocolFee: Get<Perbill>;
+
+		/// Handler for the protocol fees paid in the native currency, e.g. the DAO treasury.
+		type FeeHandler: OnUnbalanced<NegativeImbalanceOf<Self>>;
+
+		/// Handler for the protocol fees paid in an asset, e.g. the DAO treasury.
+		type AssetFeeHandler: OnUnbalanced<fungibles::CreditOf<Self::AccountId, Self::Assets>>;
+
+		/// The maximum number of milestones a collection fund is released in.
+		#[pallet::constant]
+		type MaxMilestones: Get<u32>;
+
+		/// The base deposit reserved for setting an attribute.
+		#[pallet::constant]
+		type AttributeDepositBase: Get<BalanceOf<Self>>;
+
+		/// The deposit reserved per byte of attribute key and value, and of item metadata.
+		#[pallet::constant]
+		type DepositPerByte: Get<BalanceOf<Self>>;
+
+		/// The base deposit reserved for uploading the metadata of an item.
+		#[pallet::constant]
+		type MetadataDepositBase: Get<BalanceOf<Self>>;
+
+		/// Weight information for extrinsics in this pallet.
+		type WeightInfo: WeightInfo;
 	}
 
+	/// The current storage version.
+	const STORAGE_VERSION: StorageVersion = StorageVersion::new(17);
+
 	#[pallet::pallet]
 	#[pallet::generate_store(pub(super) trait Store)]
+	#[pallet::storage_version(STORAGE_VERSION)]
 	pub struct Pallet<T>(_);
 
-	impl<T: Config> MaxEncodedLen for CollectionInfo<T> {
-		fn max_encoded_len() -> usize {
-			let len: usize = 16;
-			len
+	#[pallet::hooks]
+	impl<T: Config> Hooks<BlockNumberFor<T>> for Pallet<T> {
+		fn on_initialize(_n: T::BlockNumber) -> Weight {
+			Self::update_sale_windows()
+		}
+
+		fn on_finalize(_n: T::BlockNumber) {
+			for collection_id in <AuctionsToSettle<T>>::take() {
+				Self::settle_auction(collection_id);
+			}
 		}
-	}
 
-	impl MaxEncodedLen for NFT {
-		fn max_encoded_len() -> usize {
-			let len: usize = 16;
-			len
+		fn on_runtime_upgrade() -> Weight {
+			crate::migrations::v1::migrate::<T>() +
+				crate::migrations::v2::migrate::<T>() +
+				crate::migrations::v3::migrate::<T>() +
+				crate::migrations::v4::migrate::<T>() +
+				crate::migrations::v5::migrate::<T>() +
+				crate::migrations::v6::migrate::<T>() +
+				crate::migrations::v7::migrate::<T>() +
+				crate::migrations::v8::migrate::<T>() +
+				crate::migrations::v9::migrate::<T>() +
+				crate::migrations::v10::migrate::<T>() +
+				crate::migrations::v11::migrate::<T>() +
+				crate::migrations::v12::migrate::<T>() +
+				crate::migrations::v13::migrate::<T>() +
+				crate::migrations::v14::migrate::<T>() +
+				crate::migrations::v15::migrate::<T>() +
+				crate::migrations::v16::migrate::<T>() +
+				crate::migrations::v17::migrate::<T>()
 		}
 	}
 
-	impl Default for NFT {
+	impl<T: Config> Default for NFT<T> {
 		fn default() -> Self {
-			NFT {
-				id: 0,
-				name: format!("").as_bytes().to_vec().clone(),
-				image_url: format!("").as_bytes().to_vec().clone(),
-			}
+			NFT { id: 0, name: Default::default(), image_url: Default::default() }
 		}
 	}
 
@@ -204,6 +696,122 @@ pub mod pallet {
 			<T as frame_system::Config>::BlockNumber,
 			<T as frame_system::Config>::AccountId,
 		),
+		/// A protocol fee was taken from a contribution. [contributor, fund_index, fee]
+		ProtocolFeePaid(T::AccountId, FundIndex, BalanceOf<T>),
+		/// A contribution was returned from a failed fund. [contributor, fund_index, amount, now]
+		Withdrew(
+			<T as frame_system::Config>::AccountId,
+			FundIndex,
+			BalanceOf<T>,
+			<T as frame_system::Config>::BlockNumber,
+		),
+		/// A collection was approved for sale. [collection_id]
+		CollectionApproved(CollectionId),
+		/// A collection was rejected. [collection_id, reason]
+		CollectionRejected(CollectionId, BoundedVec<u8, T::DescriptionLimit>),
+		/// The submission deposit of a collection was returned. [collection_id, who, amount]
+		DepositReturned(CollectionId, T::AccountId, BalanceOf<T>),
+		/// The submission deposit of a rejected collection was slashed. [collection_id, amount]
+		DepositSlashed(CollectionId, BalanceOf<T>),
+		/// A draft collection was cancelled by its owner. [collection_id]
+		CollectionCancelled(CollectionId),
+		/// The creator committed to the metadata ordering of a collection. [collection_id, provenance]
+		ProvenanceCommitted(CollectionId, T::Hash),
+		/// The reveal offset of a collection was drawn. [collection_id, offset]
+		RevealOffsetDrawn(CollectionId, u32),
+		/// The final metadata of a collection was assigned to its items. [collection_id]
+		Revealed(CollectionId),
+		/// The metadata base URI of a collection was updated. [collection_id]
+		MetadataUpdated(CollectionId),
+		/// Metadata was uploaded for an item of a collection. [collection_id, nft_id]
+		ItemMetadataSet(CollectionId, NFTId),
+		/// A failed fund was reaped and its deposit returned. [fund_index, now, beneficiary]
+		Dissolved(
+			FundIndex,
+			<T as frame_system::Config>::BlockNumber,
+			<T as frame_system::Config>::AccountId,
+		),
+		/// An NFT was transferred. [collection_id, nft_id, from, to]
+		Transferred(CollectionId, NFTId, T::AccountId, T::AccountId),
+		/// An account was approved to transfer an NFT. [collection_id, nft_id, owner, delegate]
+		ApprovalGranted(CollectionId, NFTId, T::AccountId, T::AccountId),
+		/// An approval for an NFT was cancelled. [collection_id, nft_id, owner]
+		ApprovalCancelled(CollectionId, NFTId, T::AccountId),
+		/// An NFT was burned, redeeming its share of the collection fund.
+		/// [collection_id, nft_id, owner, redeemed]
+		Burned(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// The royalty of a collection was updated. [collection_id]
+		RoyaltySet(CollectionId),
+		/// A royalty was paid on the sale of an NFT. [collection_id, nft_id, recipient, amount]
+		RoyaltyPaid(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// An NFT was listed for sale. [collection_id, nft_id, seller, price]
+		Listed(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// The listing of an NFT was cancelled. [collection_id, nft_id]
+		ListingCancelled(CollectionId, NFTId),
+		/// A listed NFT was sold. [collection_id, nft_id, seller, buyer, price]
+		Sold(CollectionId, NFTId, T::AccountId, T::AccountId, BalanceOf<T>),
+		/// A bid was placed in an English auction. [collection_id, bidder, amount]
+		BidPlaced(CollectionId, T::AccountId, BalanceOf<T>),
+		/// An English auction was settled and its item minted to the winner.
+		/// [collection_id, nft_id, winner, amount]
+		AuctionSettled(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// An English auction could not be settled and the highest bid was refunded.
+		/// [collection_id, bidder, amount]
+		AuctionRefunded(CollectionId, T::AccountId, BalanceOf<T>),
+		/// The presale allowlist of a collection was set. [collection_id, merkle_root, price]
+		PresaleSet(CollectionId, T::Hash, BalanceOf<T>),
+		/// The sale phase of a collection changed. [collection_id, phase]
+		SalePhaseChanged(CollectionId, SalePhase),
+		/// Allowlisted items were minted. [collection_id, account, quantity]
+		AllowlistMinted(CollectionId, T::AccountId, u32),
+		/// A collection was frozen. [collection_id]
+		CollectionFrozen(CollectionId),
+		/// A collection was thawed. [collection_id]
+		CollectionThawed(CollectionId),
+		/// An item was frozen. [collection_id, nft_id]
+		ItemFrozen(CollectionId, NFTId),
+		/// An item was thawed. [collection_id, nft_id]
+		ItemThawed(CollectionId, NFTId),
+		/// The roles of a collection changed. [collection_id, issuer, admin, freezer]
+		TeamChanged(CollectionId, T::AccountId, T::AccountId, T::AccountId),
+		/// The owner offered the ownership of a collection. [collection_id, new_owner]
+		OwnershipTransferProposed(CollectionId, T::AccountId),
+		/// The ownership of a collection was accepted. [collection_id, new_owner]
+		OwnershipTransferred(CollectionId, T::AccountId),
+		/// An attribute was set. [collection_id, maybe_nft_id, key, value]
+		AttributeSet(
+			CollectionId,
+			Option<NFTId>,
+			BoundedVec<u8, T::KeyLimit>,
+			BoundedVec<u8, T::ValueLimit>,
+		),
+		/// An attribute was cleared. [collection_id, maybe_nft_id, key]
+		AttributeCleared(CollectionId, Option<NFTId>, BoundedVec<u8, T::KeyLimit>),
+		/// An item holder voted on a milestone. [collection_id, milestone, nft_id, approve]
+		MilestoneVoted(CollectionId, u32, NFTId, bool),
+		/// A milestone tranche was released to the creator. [collection_id, milestone, amount]
+		MilestoneReleased(CollectionId, u32, BalanceOf<T>),
+		/// A milestone was rejected or missed its deadline. [collection_id, milestone]
+		MilestoneFailed(CollectionId, u32),
+		/// The share of the unreleased funds of an item was refunded to its holder.
+		/// [collection_id, nft_id, holder, amount]
+		MilestoneRefund(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// A collection and all of its items were destroyed. [collection_id]
+		Destroyed(CollectionId),
+		/// The sale window of a collection has opened. [collection_id]
+		SaleWindowOpened(CollectionId),
+		/// The sale window of a collection has closed. [collection_id]
+		SaleWindowClosed(CollectionId),
+		/// An item was locked and split into shares. [collection_id, nft_id, owner, asset_id, supply]
+		Fractionalized(CollectionId, NFTId, T::AccountId, T::AssetId, BalanceOf<T>),
+		/// The full share supply was burned to release an item. [collection_id, nft_id, who]
+		FractionsRedeemed(CollectionId, NFTId, T::AccountId),
+		/// A fractionalized item was bought out at its reserve price.
+		/// [collection_id, nft_id, buyer, price]
+		BoughtOut(CollectionId, NFTId, T::AccountId, BalanceOf<T>),
+		/// A shareholder burned their shares for their part of the buyout price.
+		/// [collection_id, nft_id, holder, shares, amount]
+		BuyoutClaimed(CollectionId, NFTId, T::AccountId, BalanceOf<T>, BalanceOf<T>),
 	}
 
 	// Errors inform users that something went wrong.
@@ -228,57 +836,327 @@ pub mod pallet {
 		NotFundOwner,
 
 		ExceedMaxNFTOwned,
+		// NFT is not existed in the collection
+		NFTNotExists,
+		// Require NFT owner to execute some pallet calls
+		NotNFTOwner,
+		// The caller is not approved to transfer the NFT
+		NotApproved,
+		// The NFT has no approved delegate
+		NoApproval,
+		// Can not transfer an NFT to its current owner
+		TransferToSelf,
+		// The sale window must end after it starts
+		InvalidSaleWindow,
+		// Too many collections have a pending or running sale window
+		TooManySaleWindows,
+		// The sale window of the collection has not started yet
+		SaleNotStarted,
+		// The sale window of the collection has ended
+		SaleEnded,
+		// The fund must end after the current block
+		FundEndTooEarly,
+		// The fund no longer accepts contributions
+		ContributionPeriodOver,
+		// The fund has not ended yet
+		FundStillActive,
+		// The fund has reached its goal, so contributions are not refundable
+		FundSucceeded,
+		// The fund has not reached its goal
+		GoalNotReached,
+		// The account has no contribution in the fund
+		NoContribution,
+		// The fund still holds contributions that must be refunded first
+		FundNotEmpty,
+		// The metadata URI is longer than `UriLimit`
+		UriTooLong,
+		// The name is longer than `NameLimit`
+		NameTooLong,
+		// The description is longer than `DescriptionLimit`
+		DescriptionTooLong,
+		// The item index is outside of the collection
+		InvalidItemIndex,
+		// Item metadata can only be uploaded before the collection is approved
+		CollectionNotInDraftState,
+		// The collection is frozen: its items can not be transferred and its metadata can not change
+		CollectionFrozen,
+		// The collection has no delayed reveal commitment
+		NoProvenance,
+		// The collection has already been revealed
+		AlreadyRevealed,
+		// The sale window of the collection has not ended yet
+		SaleNotEnded,
+		// The revealed metadata does not match the committed provenance hash
+		InvalidProvenance,
+		// The revealed metadata must contain one URI per item
+		MetadataLengthMismatch,
+		// Items of a redeemable collection can only be redeemed once its fund succeeded
+		FundFailed,
+		// The NFT is not listed for sale
+		NotListed,
+		// The NFT is listed for sale and can only be moved by its owner
+		NFTListed,
+		// The listing price is higher than the maximum price of the buyer
+		PriceTooHigh,
+		// The seller can not buy their own NFT
+		BuyerIsSeller,
+		// Dutch auctions need a floor price below the start price, English auctions a single item
+		InvalidSaleMode,
+		// Items of an English auction collection can only be bought with `bid`
+		AuctionOnly,
+		// The collection is not sold by English auction
+		NotAnAuction,
+		// The bid must reach the reserve price and exceed the highest bid
+		BidTooLow,
+		// The collection is in its allowlist phase
+		PublicSaleNotStarted,
+		// The collection is not in its allowlist phase
+		NotInAllowlistPhase,
+		// The collection has no presale
+		NoPresale,
+		// The Merkle proof does not match the allowlist root
+		InvalidMerkleProof,
+		// Minting would exceed the allowlist quota of the account
+		AllowlistQuotaExceeded,
+		// Not enough items are left in the collection
+		SoldOut,
+		// The mint quantity must be between one and `MaxMintPerTx`
+		InvalidMintQuantity,
+		// The account has minted `max_per_account` items of the collection
+		MintLimitReached,
+		// The item is frozen and can not be transferred
+		ItemFrozen,
+		// Items of a soulbound collection can never be transferred
+		Soulbound,
+		// The collection or item is not frozen
+		NotFrozen,
+		// The caller does not have the collection role required for this call
+		NoPermission,
+		// The caller was not offered the ownership of the collection
+		NotOfferedOwnership,
+		// Collections are created with the next free id
+		InvalidCollectionId,
+		// The destroy witness does not match the collection
+		BadWitness,
+		// The collection fund must be dispensed or dissolved first
+		FundExists,
+		// The attribute key is longer than `KeyLimit`
+		KeyTooLong,
+		// The attribute value is longer than `ValueLimit`
+		ValueTooLong,
+		// The attribute does not exist
+		NoAttribute,
+		// The collection has more milestones than `MaxMilestones`
+		TooManyMilestones,
+		// Milestone amounts must add up to the goal and deadlines must increase after the fund end
+		InvalidMilestones,
+		// The fund is released in milestones
+		FundHasMilestones,
+		// The fund is not released in milestones, or all of them were released
+		NoMilestone,
+		// The item already voted on the current milestone
+		AlreadyVoted,
+		// The current milestone was rejected or missed its deadline
+		MilestoneFailed,
+		// The current milestone is not approved by a majority of the items
+		MilestoneNotApproved,
+		// The current milestone has not failed
+		MilestoneNotFailed,
+		// The item was already refunded
+		AlreadyRefunded,
+		// The account can not pay the mint price
+		InsufficientBalance,
+		// The collection name has no letters, digits or non-ASCII characters to build a slug from
+		InvalidCollectionName,
+		// Another collection has a name with the same slug
+		CollectionNameTaken,
+		// Fractions need a non-zero share supply
+		InvalidSupply,
+		// The item is not fractionalized
+		NotFractionalized,
+		// Redeeming an item requires holding its full share supply
+		NotAllShares,
+		// The fractionalized item has no reserve price
+		NoReservePrice,
+		// The fractionalized item was already bought out
+		AlreadyBoughtOut,
+		// The fractionalized item was not bought out
+		NotBoughtOut,
+		// The account holds no shares of the item
+		NoShares,
+		// The buyout payout for the shares is below the existential deposit
+		PayoutTooSmall,
+		// The fund of a redeemable collection is paid out to the holders burning their items
+		FundRedeemable,
+		// The item, or an item of the collection, is fractionalized
+		Fractionalized,
 	}
 
 	#[pallet::call]
 	impl<T: Config> Pallet<T> {
 		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[transactional]
 		pub fn register_collection(
 			origin: OriginFor<T>,
-			name: Vec<u8>,
-			description: Vec<u8>,
-			number_of_items: u16,
-			mint_fee: BalanceOf<T>,
+			config: CollectionConfigOf<T>,
 		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/originsowner
 			let who = ensure_signed(origin)?;
 
-			let collection_id =
-				Self::new_collection(&who, name, description, number_of_items, mint_fee).unwrap();
+			let collection_id = Self::new_collection(&who, config)?;
 			Self::deposit_event(Event::CollectionRegistered(collection_id.clone()));
 
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		#[transactional]
 		pub fn approve_collection(
 			origin: OriginFor<T>,
 			collection_id: CollectionId,
-			start_date: u32,
-			end_date: u32,
+			start_date: u64,
+			end_date: u64,
+			sale_window_unit: SaleWindowUnit,
 		) -> DispatchResult {
-			// let who = ensure_root(origin)?;
-			let who = ensure_signed(origin)?;
+			T::ApproveOrigin::ensure_origin(origin)?;
 
 			// Get collection info
 			let mut collection =
 				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
 
+			ensure!(
+				collection.project_status == ProjectStatus::Draft,
+				Error::<T>::CollectionNotInDraftState
+			);
+			ensure!(start_date < end_date, Error::<T>::InvalidSaleWindow);
+
+			Self::return_deposit(collection_id)?;
+			<SaleWindows<T>>::try_append((collection_id, false))
+				.map_err(|_| <Error<T>>::TooManySaleWindows)?;
+
+			collection.project_status = ProjectStatus::Approved;
+			collection.start_date = Some(start_date);
+			collection.end_date = Some(end_date);
+			collection.sale_window_unit = sale_window_unit;
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::CollectionApproved(collection_id));
+			Ok(())
+		}
+
+		/// Reject a draft collection. The `SubmissionDeposit` is slashed if `slash_deposit` is set,
+		/// otherwise it is returned to the owner. The collection fund is reaped and the collection
+		/// name freed either way.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 5))]
+		pub fn reject_collection(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			reason: Vec<u8>,
+			slash_deposit: bool,
+		) -> DispatchResult {
+			T::ApproveOrigin::ensure_origin(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(
+				collection.project_status == ProjectStatus::Draft,
+				Error::<T>::CollectionNotInDraftState
+			);
+			let reason: BoundedVec<u8, T::DescriptionLimit> =
+				reason.try_into().map_err(|()| Error::<T>::DescriptionTooLong)?;
+
+			if slash_deposit {
+				Self::slash_deposit(collection_id)?;
+			} else {
+				Self::return_deposit(collection_id)?;
+			}
+			// A draft collection can not be minted, so its fund holds no contributions.
+			if let Some(fund) = Self::funds(collection_id) {
+				Self::close_fund(collection_id, &fund);
+			}
+			// The name of a rejected collection is free for a new submission.
+			Self::release_name(collection_id, &collection);
+			collection.slug = Default::default();
+
+			collection.project_status = ProjectStatus::Rejected;
+			<Collections<T>>::insert(&collection_id, collection);
+			<RejectionReasons<T>>::insert(&collection_id, &reason);
+
+			Self::deposit_event(Event::CollectionRejected(collection_id, reason));
+			Ok(())
+		}
+
+		/// Cancel a draft collection. The `SubmissionDeposit` is unreserved and the collection,
+		/// its fund and everything stored about them are removed.
+		///
+		/// `witness` must hold the number of items of the collection and at least the number of
+		/// its item metadata and attributes, see `cancel_witness`.
+		#[pallet::weight(T::WeightInfo::cancel_collection(
+			witness.items,
+			witness.item_metadata,
+			witness.attributes
+		))]
+		#[transactional]
+		pub fn cancel_collection(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			witness: CancelWitness,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
 			ensure!(who == collection.owner, Error::<T>::NotFundOwner);
+			ensure!(
+				collection.project_status == ProjectStatus::Draft,
+				Error::<T>::CollectionNotInDraftState
+			);
+			ensure!(collection.number_of_minted == witness.items, Error::<T>::BadWitness);
+			ensure!(
+				<ItemMetadata<T>>::iter_prefix(collection_id)
+					.nth(witness.item_metadata as usize)
+					.is_none(),
+				Error::<T>::BadWitness
+			);
+			ensure!(
+				<Attributes<T>>::iter_prefix((collection_id,))
+					.nth(witness.attributes as usize)
+					.is_none(),
+				Error::<T>::BadWitness
+			);
+			ensure!(
+				<Fractions<T>>::iter_prefix(collection_id).next().is_none(),
+				Error::<T>::Fractionalized
+			);
 
-			if collection.project_status != ProjectStatus::Approved {
-				collection.project_status = ProjectStatus::Approved;
-				collection.start_date = Some(start_date);
-				collection.end_date = Some(end_date);
-				<Collections<T>>::insert(&collection_id, collection);
+			// A draft collection can not be minted, so its fund holds no contributions.
+			if let Some(fund) = Self::funds(collection_id) {
+				Self::close_fund(collection_id, &fund);
+				if !fund.deposit.is_zero() {
+					Self::deposit_event(Event::DepositReturned(
+						collection_id,
+						fund.beneficiary,
+						fund.deposit,
+					));
+				}
 			}
+			Self::remove_collection(collection_id);
+
+			Self::deposit_event(Event::CollectionCancelled(collection_id));
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn mint(origin: OriginFor<T>, collection_id: CollectionId) -> DispatchResult {
+		/// Mint `quantity` items, at most `MaxMintPerTx`. The call fails with `SoldOut` if fewer
+		/// than `quantity` items remain.
+		#[pallet::weight(T::WeightInfo::mint(*quantity))]
+		#[transactional]
+		pub fn mint(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			quantity: u32,
+		) -> DispatchResult {
 			// Check that the extrinsic was signed and get the signer.
 			// This function will return an error if the extrinsic is not signed.
 			// https://docs.substrate.io/v3/runtime/origins
@@ -291,129 +1169,1730 @@ pub mod pallet {
 
 			// Ensure collection is approved
 			ensure!(collection.project_status == ProjectStatus::Approved, Error::<T>::CollectionIsInDraftState);
+			ensure!(collection.sale_phase == SalePhase::Public, Error::<T>::PublicSaleNotStarted);
+			Self::ensure_sale_open(&collection)?;
+			ensure!(
+				quantity > 0 && quantity <= T::MaxMintPerTx::get(),
+				Error::<T>::InvalidMintQuantity
+			);
 
-			if collection.number_of_minted < collection.number_of_items {
-				let mint_fee = collection.mint_fee;
-				let nft = Self::generate_collection_nft(collection.number_of_minted);
-				// Store data on chain
-				// let mut nfts =  Self::get_nfts.iter_prefix_values(collection_id)
-				let nft_id = nft.id;
-				NFTs::<T>::insert(collection_id, nft_id, &nft);
-				log::info!(
-					"A NFT is minted with ID: {:?} in collection id: {:?}",
-					nft_id,
-					collection_id
-				);
-
-				collection.number_of_minted += 1;
-				<Collections<T>>::insert(&collection_id, collection);
+			ensure!(
+				Self::next_nft_id(&collection).saturating_add(quantity) <=
+					collection.number_of_items,
+				Error::<T>::SoldOut
+			);
 
-				<NFTOwned<T>>::insert(nft_id, &who);
-				<NFTMap<T>>::insert(nft_id, &nft);
-				<CollectionOwnerNFT<T>>::try_mutate(collection_id, &who, |nft_vec| {
-					nft_vec.try_push(nft_id)
-				}).map_err(|_| <Error<T>>::ExceedMaxNFTOwned)?;
+			// The price only changes between blocks, so every item of the batch costs the same.
+			let mint_fee = Self::mint_price(collection_id, &collection)?;
+			Self::ensure_can_pay(collection_id, &who, mint_fee.saturating_mul(quantity.into()))?;
 
-				let _ = Self::contribute(&who.clone(), collection_id, mint_fee);
-			} else {
+			for _ in 0..quantity {
+				Self::insert_nft(collection_id, &mut collection, &who)?;
+				Self::contribute(&who, collection_id, mint_fee).map_err(|e| e.error)?;
 			}
 			Ok(())
 		}
 
-		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
-		pub fn dispense_fund(origin: OriginFor<T>, index: FundIndex, beneficiary: T::AccountId) -> DispatchResultWithPostInfo {
+		/// Mint `quantity` items at the presale price. `proof` proves that the leaf
+		/// `hash(caller, quota)` is part of the allowlist Merkle tree, where pairs of nodes are
+		/// hashed in ascending order. An account can mint up to `quota` items in the presale.
+		#[pallet::weight(T::WeightInfo::mint_allowlisted(*quantity, proof.len() as u32))]
+		#[transactional]
+		pub fn mint_allowlisted(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			proof: Vec<T::Hash>,
+			quota: u32,
+			quantity: u32,
+		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			let now = <frame_system::Pallet<T>>::block_number();
-			let fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
 
-			ensure!(who == fund.beneficiary, Error::<T>::NotFundOwner);
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(
+				collection.project_status == ProjectStatus::Approved,
+				Error::<T>::CollectionIsInDraftState
+			);
+			ensure!(collection.sale_phase == SalePhase::Allowlist, Error::<T>::NotInAllowlistPhase);
+			ensure!(
+				!matches!(collection.sale_mode, SaleMode::EnglishAuction { .. }),
+				Error::<T>::AuctionOnly
+			);
+			Self::ensure_sale_open(&collection)?;
+			ensure!(
+				quantity > 0 && quantity <= T::MaxMintPerTx::get(),
+				Error::<T>::InvalidMintQuantity
+			);
+			let presale = collection.presale.clone().ok_or(<Error<T>>::NoPresale)?;
 
-			Self::dispense(index, beneficiary);
+			let leaf = T::Hashing::hash_of(&(&who, quota));
+			ensure!(
+				Self::merkle_root_of(leaf, &proof) == presale.merkle_root,
+				Error::<T>::InvalidMerkleProof
+			);
+			let claimed = Self::allowlist_claimed(collection_id, &who).saturating_add(quantity);
+			ensure!(claimed <= quota, Error::<T>::AllowlistQuotaExceeded);
+			ensure!(
+				Self::next_nft_id(&collection).saturating_add(quantity) <=
+					collection.number_of_items,
+				Error::<T>::SoldOut
+			);
+			Self::ensure_can_pay(
+				collection_id,
+				&who,
+				presale.price.saturating_mul(quantity.into()),
+			)?;
 
-			Self::deposit_event(Event::Dispensed(index, now, who));
+			for _ in 0..quantity {
+				Self::insert_nft(collection_id, &mut collection, &who)?;
+				Self::contribute(&who, collection_id, presale.price).map_err(|e| e.error)?;
+			}
+			<AllowlistClaimed<T>>::insert(collection_id, &who, claimed);
 
-			Ok(().into())
+			Self::deposit_event(Event::AllowlistMinted(collection_id, who, quantity));
+			Ok(())
 		}
-	}
 
-	impl<T: Config> Pallet<T> {
-		pub fn get_launchpad_collections() -> u32 {
-			10
-		}
+		/// Set the presale allowlist and price, putting the collection in its allowlist phase.
+		/// Only allowed before approval or while the allowlist phase lasts.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_presale(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			merkle_root: T::Hash,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-		fn new_collection(
-			owner: &T::AccountId,
-			name: Vec<u8>,
-			description: Vec<u8>,
-			number_of_items: u16,
-			mint_fee: BalanceOf<T>,
-		) -> Result<CollectionId, DispatchError> {
-			let collection_id = <FundCount<T>>::get();
-			// not protected against overflow, see safemath section
-			<FundCount<T>>::put(collection_id + 1);
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.issuer, Error::<T>::NoPermission);
+			ensure!(
+				collection.project_status == ProjectStatus::Draft ||
+					collection.sale_phase == SalePhase::Allowlist,
+				Error::<T>::NotInAllowlistPhase
+			);
+			// Auction items are only sold to the highest bidder.
+			ensure!(
+				!matches!(collection.sale_mode, SaleMode::EnglishAuction { .. }),
+				Error::<T>::AuctionOnly
+			);
 
-			let collection_info = CollectionInfo::<T> {
-				id: collection_id,
-				owner: owner.clone(),
-				name,
-				description,
-				number_of_items,
-				project_status: ProjectStatus::Draft,
-				is_frozen: false,
-				number_of_minted: 0,
-				mint_fee,
-				start_date: None,
-				end_date: None,
-			};
+			collection.presale = Some(PresaleInfo { merkle_root, price });
+			collection.sale_phase = SalePhase::Allowlist;
+			<Collections<T>>::insert(&collection_id, collection);
 
-			// Check if the collection id does not already exist in our storage map
-			ensure!(Self::get_collections(&collection_id) == None, <Error<T>>::CollectionExists);
+			Self::deposit_event(Event::PresaleSet(collection_id, merkle_root, price));
+			Ok(())
+		}
 
-			log::info!("A collection is created with ID: {:?}", collection_id);
+		/// End the allowlist phase of a collection and open minting to everyone.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn start_public_sale(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			// Save collection on-chain
-			Collections::<T>::insert(collection_id, collection_info);
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.issuer, Error::<T>::NoPermission);
+			ensure!(collection.sale_phase == SalePhase::Allowlist, Error::<T>::NotInAllowlistPhase);
 
-			// Create Fund
-			let current_block_number = <frame_system::Pallet<T>>::block_number();
-			Self::create_fund(&owner, collection_id, current_block_number);
+			collection.sale_phase = SalePhase::Public;
+			<Collections<T>>::insert(&collection_id, collection);
 
-			Ok(collection_id)
+			Self::deposit_event(Event::SalePhaseChanged(collection_id, SalePhase::Public));
+			Ok(())
 		}
 
-		fn generate_collection_nft(total_nft: u16) -> NFT {
-			let index = Self::gen_nft_index();
-			log::info!("A collection NFT Index is created: {:?}", index);
+		/// Bid on the item of an English auction collection. The bid is reserved and the
+		/// previous highest bid refunded.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn bid(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
 
-			// Generate 10 NFTs for a collection
-			let images: [&str; 10] = [
-				"https://gateway.pinata.cloud/ipfs/QmXYEXK4gNtnydgXBBa37YVhF1Zyi3frYMNuLP2YwNZ6GT",
-				"https://gateway.pinata.cloud/ipfs/QmTvzJ6bWkB87BtbUMDnuTE2WqBp2FbDXL6epr6ysRPiXm",
-				"https://gateway.pinata.cloud/ipfs/Qmddbben3DWctUaT9kqR6zckPj5DGdNcGQoxq3rMvsDYiL",
-				"https://gateway.pinata.cloud/ipfs/QmaiKJcgeWgY5XX6D41pcZti8NYuUMYKQrPbqbBNfBvLpH",
-				"https://gateway.pinata.cloud/ipfs/QmaVjEKkUJ5UxcecD3dYAGMCfj7whT6wf2ZBCAF24d3hgf",
-				"https://gateway.pinata.cloud/ipfs/Qmcu8x8Hsu4ht7jmWe1c3Dh93p4qn97tenR1uFp1KkN1oC",
-				"https://gateway.pinata.cloud/ipfs/QmVXYnjPszRpHEDrNFK1vVAqbQioy6fjweCbq8JHrpGs6j",
-				"https://gateway.pinata.cloud/ipfs/QmXm5UZivCrSNoB4VLMcwtkyejNcXGTZBtEJXAq8v3ZFFb",
-				"https://gateway.pinata.cloud/ipfs/QmYmte4mZEVLNRtmHtixnasLna7jP86ftZzTsSygvLfNBf",
-				"https://gateway.pinata.cloud/ipfs/QmR6uKp5gPWkKHEwWdLhbcjgkE1GWBW49AhYi9V8u4Ly5W",
-			];
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(
+				collection.project_status == ProjectStatus::Approved,
+				Error::<T>::CollectionIsInDraftState
+			);
+			ensure!(collection.sale_phase == SalePhase::Public, Error::<T>::PublicSaleNotStarted);
+			Self::ensure_sale_open(&collection)?;
+			let reserve_price = match collection.sale_mode {
+				SaleMode::EnglishAuction { reserve_price } => reserve_price,
+				_ => return Err(Error::<T>::NotAnAuction.into()),
+			};
+			ensure!(amount >= reserve_price, Error::<T>::BidTooLow);
 
-			NFT {
-				id: total_nft,
-				name: format!("Item #{}", total_nft).as_bytes().to_vec().clone(),
-				image_url: images[index as usize].as_bytes().to_vec().clone(),
+			let previous = Self::highest_bid(collection_id);
+			if let Some((_, highest)) = &previous {
+				ensure!(amount > *highest, Error::<T>::BidTooLow);
 			}
-		}
+			T::Currency::reserve(&who, amount)?;
+			if let Some((bidder, highest)) = previous {
+				T::Currency::unreserve(&bidder, highest);
+			}
+			<Bids<T>>::insert(collection_id, (who.clone(), amount));
 
-		fn gen_nft_index() -> u8 {
-			let random = T::CollectionRandomness::random(&b"NFT Indexing"[..]).0;
-			random.as_ref()[0] % 10
+			Self::deposit_event(Event::BidPlaced(collection_id, who, amount));
+			Ok(())
+		}
+
+		/// Upload the metadata URI of a single item. Only allowed before the collection is approved.
+		/// A deposit of `MetadataDepositBase` plus `DepositPerByte` for every byte of the URI is
+		/// reserved from the admin until the item is minted, replacing the deposit of any previous
+		/// URI.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn set_item_metadata(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			uri: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(
+				collection.project_status == ProjectStatus::Draft,
+				Error::<T>::CollectionNotInDraftState
+			);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+			ensure!(nft_id < collection.number_of_items, Error::<T>::InvalidItemIndex);
+
+			let uri: BoundedVec<u8, T::UriLimit> =
+				uri.try_into().map_err(|()| Error::<T>::UriTooLong)?;
+			let deposit = T::DepositPerByte::get()
+				.saturating_mul((uri.len() as u32).into())
+				.saturating_add(T::MetadataDepositBase::get());
+			T::Currency::reserve(&who, deposit)?;
+			if let Some((_, Some((old_depositor, old_deposit)))) =
+				<ItemMetadata<T>>::get(collection_id, nft_id)
+			{
+				T::Currency::unreserve(&old_depositor, old_deposit);
+			}
+			<ItemMetadata<T>>::insert(collection_id, nft_id, (uri, Some((who, deposit))));
+
+			Self::deposit_event(Event::ItemMetadataSet(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Replace the metadata base URI of a collection. Items minted afterwards derive their URI
+		/// from the new base, which must leave room for the item ids. Locked once the collection is
+		/// frozen.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn update_metadata(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			base_uri: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+
+			Self::ensure_uri_room(&base_uri, collection.number_of_items)?;
+			collection.base_uri = base_uri.try_into().map_err(|()| Error::<T>::UriTooLong)?;
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::MetadataUpdated(collection_id));
+			Ok(())
+		}
+
+		/// Freeze a collection: its items can not be transferred and its metadata, royalty and
+		/// reveal are locked until it is thawed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn freeze_collection(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.freezer, Error::<T>::NoPermission);
+
+			collection.is_frozen = true;
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::CollectionFrozen(collection_id));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn thaw_collection(origin: OriginFor<T>, collection_id: CollectionId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(collection.is_frozen, Error::<T>::NotFrozen);
+
+			collection.is_frozen = false;
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::CollectionThawed(collection_id));
+			Ok(())
+		}
+
+		/// Freeze a single item so it can not be transferred until it is thawed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn freeze(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.freezer, Error::<T>::NoPermission);
+			ensure!(<NFTOwned<T>>::contains_key(collection_id, nft_id), Error::<T>::NFTNotExists);
+
+			<FrozenItems<T>>::insert(collection_id, nft_id, true);
+
+			Self::deposit_event(Event::ItemFrozen(collection_id, nft_id));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn thaw(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(Self::is_item_frozen(collection_id, nft_id), Error::<T>::NotFrozen);
+
+			<FrozenItems<T>>::remove(collection_id, nft_id);
+
+			Self::deposit_event(Event::ItemThawed(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Assign the issuer, admin and freezer roles of a collection.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_team(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			issuer: T::AccountId,
+			admin: T::AccountId,
+			freezer: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.owner, Error::<T>::NotFundOwner);
+
+			collection.issuer = issuer.clone();
+			collection.admin = admin.clone();
+			collection.freezer = freezer.clone();
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::TeamChanged(collection_id, issuer, admin, freezer));
+			Ok(())
+		}
+
+		/// Offer the ownership of a collection to `new_owner`. It only moves once `new_owner`
+		/// calls `accept_collection_ownership`. Offering it to the current owner cancels the
+		/// offer.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn transfer_collection_ownership(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			new_owner: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.owner, Error::<T>::NotFundOwner);
+
+			if new_owner == who {
+				<OwnershipAcceptance<T>>::remove(collection_id);
+			} else {
+				<OwnershipAcceptance<T>>::insert(collection_id, &new_owner);
+			}
+
+			Self::deposit_event(Event::OwnershipTransferProposed(collection_id, new_owner));
+			Ok(())
+		}
+
+		/// Accept the ownership of a collection. The new owner also becomes the beneficiary of
+		/// the collection fund: the outstanding `SubmissionDeposit` is reserved from the new owner
+		/// and unreserved for the previous one.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 3))]
+		pub fn accept_collection_ownership(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(
+				Self::ownership_acceptance(collection_id).as_ref() == Some(&who),
+				Error::<T>::NotOfferedOwnership
+			);
+
+			if let Some(mut fund) = Self::funds(collection_id) {
+				// The new owner reserves the submission deposit before the old owner's is freed.
+				T::Currency::reserve(&who, fund.deposit)?;
+				T::Currency::unreserve(&fund.beneficiary, fund.deposit);
+				fund.beneficiary = who.clone();
+				<Funds<T>>::insert(collection_id, &fund);
+			}
+			<OwnershipAcceptance<T>>::remove(collection_id);
+			collection.owner = who.clone();
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::OwnershipTransferred(collection_id, who));
+			Ok(())
+		}
+
+		/// Set an attribute of a collection, or of one of its items if `maybe_nft_id` is set. A
+		/// deposit of `AttributeDepositBase` plus `DepositPerByte` for every byte of the key and
+		/// value is reserved from the admin, replacing the deposit of any previous value.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn set_attribute(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			maybe_nft_id: Option<NFTId>,
+			key: Vec<u8>,
+			value: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+			if let Some(nft_id) = maybe_nft_id {
+				ensure!(nft_id < collection.number_of_items, Error::<T>::InvalidItemIndex);
+			}
+
+			Self::do_set_attribute(collection_id, maybe_nft_id, &key, &value, Some(&who))
+		}
+
+		/// Clear an attribute of a collection or item and unreserve its deposit.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn clear_attribute(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			maybe_nft_id: Option<NFTId>,
+			key: Vec<u8>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+			let key: BoundedVec<u8, T::KeyLimit> =
+				key.try_into().map_err(|()| Error::<T>::KeyTooLong)?;
+
+			let (_, deposit) = <Attributes<T>>::take((collection_id, maybe_nft_id, &key))
+				.ok_or(Error::<T>::NoAttribute)?;
+			if let Some((depositor, amount)) = deposit {
+				T::Currency::unreserve(&depositor, amount);
+			}
+
+			Self::deposit_event(Event::AttributeCleared(collection_id, maybe_nft_id, key));
+			Ok(())
+		}
+
+		/// Approve or reject the current milestone of a collection with one of its items. Voting
+		/// opens once the fund has succeeded.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 2))]
+		pub fn vote_milestone(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			approve: bool,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+
+			let (collection, milestone, mut progress) = Self::current_milestone(collection_id)?;
+			ensure!(
+				!Self::milestone_has_failed(&collection, &milestone, &progress),
+				Error::<T>::MilestoneFailed
+			);
+			let key = (collection_id, progress.current, nft_id);
+			ensure!(!<MilestoneVotes<T>>::contains_key(key), Error::<T>::AlreadyVoted);
+
+			<MilestoneVotes<T>>::insert(key, approve);
+			if approve {
+				progress.ayes = progress.ayes.saturating_add(1);
+			} else {
+				progress.nays = progress.nays.saturating_add(1);
+			}
+			let current = progress.current;
+			<CurrentMilestone<T>>::insert(collection_id, progress);
+
+			Self::deposit_event(Event::MilestoneVoted(collection_id, current, nft_id, approve));
+			Ok(())
+		}
+
+		/// Release the tranche of the current milestone to the fund beneficiary once the holders
+		/// of a majority of the items approved it. The last milestone releases everything left in
+		/// the fund and closes it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 5))]
+		pub fn release_milestone(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			let (collection, milestone, progress) = Self::current_milestone(collection_id)?;
+			ensure!(
+				!Self::milestone_has_failed(&collection, &milestone, &progress),
+				Error::<T>::MilestoneFailed
+			);
+			ensure!(
+				progress.ayes.saturating_mul(2) > collection.number_of_minted,
+				Error::<T>::MilestoneNotApproved
+			);
+
+			let mut fund = Self::funds(collection_id).ok_or(Error::<T>::InvalidFundIndex)?;
+			let milestones = Self::milestones(collection_id);
+			let is_last = progress.current as usize + 1 >= milestones.len();
+			let amount = if is_last { fund.raised } else { milestone.amount.min(fund.raised) };
+			Self::pay_out(collection_id, &fund, &fund.beneficiary, amount)?;
+			Self::deposit_event(Event::MilestoneReleased(collection_id, progress.current, amount));
+
+			if is_last {
+				Self::close_fund(collection_id, &fund);
+				<Milestones<T>>::remove(collection_id);
+				<CurrentMilestone<T>>::remove(collection_id);
+				let _ = <MilestoneVotes<T>>::remove_prefix((collection_id,), None);
+			} else {
+				fund.raised = fund.raised.saturating_sub(amount);
+				<Funds<T>>::insert(collection_id, &fund);
+				<CurrentMilestone<T>>::insert(
+					collection_id,
+					MilestoneProgress { current: progress.current + 1, ..Default::default() },
+				);
+			}
+			Ok(())
+		}
+
+		/// Refund the share of the unreleased funds of an item to its holder once the current
+		/// milestone was rejected by a majority of the items or missed its deadline.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 4))]
+		pub fn claim_milestone_refund(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+			ensure!(!Self::milestone_refunded(collection_id, nft_id), Error::<T>::AlreadyRefunded);
+
+			let (collection, milestone, mut progress) = Self::current_milestone(collection_id)?;
+			let mut fund = Self::funds(collection_id).ok_or(Error::<T>::InvalidFundIndex)?;
+			let refund = match progress.refund_per_item {
+				Some(refund) => refund,
+				None => {
+					ensure!(
+						Self::milestone_has_failed(&collection, &milestone, &progress),
+						Error::<T>::MilestoneNotFailed
+					);
+					let outstanding: BalanceOf<T> = collection.number_of_minted.max(1).into();
+					let refund = fund.raised / outstanding;
+					progress.refund_per_item = Some(refund);
+					<CurrentMilestone<T>>::insert(collection_id, &progress);
+					Self::deposit_event(Event::MilestoneFailed(collection_id, progress.current));
+					refund
+				},
+			};
+
+			Self::pay_out(collection_id, &fund, &who, refund)?;
+			fund.raised = fund.raised.saturating_sub(refund);
+			<Funds<T>>::insert(collection_id, &fund);
+			<MilestoneRefunded<T>>::insert(collection_id, nft_id, true);
+
+			Self::deposit_event(Event::MilestoneRefund(collection_id, nft_id, who, refund));
+			Ok(())
+		}
+
+		/// Set or clear the royalty charged on secondary sales. Only allowed until the collection
+		/// is frozen.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn set_royalty(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			royalty: Option<RoyaltyInfo<T::AccountId>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+
+			collection.royalty = royalty;
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::RoyaltySet(collection_id));
+			Ok(())
+		}
+
+		/// Commit to the ordered item metadata of a draft collection. Its items are minted as
+		/// placeholders until `reveal` is called after the sale.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn commit_provenance(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			provenance: T::Hash,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(
+				collection.project_status == ProjectStatus::Draft,
+				Error::<T>::CollectionNotInDraftState
+			);
+
+			<Reveals<T>>::insert(
+				collection_id,
+				RevealInfo { provenance, offset: None, revealed: false },
+			);
+
+			Self::deposit_event(Event::ProvenanceCommitted(collection_id, provenance));
+			Ok(())
+		}
+
+		/// Reveal the ordered item metadata committed with `commit_provenance`. Item `i` receives
+		/// `metadata[(i + offset) % number_of_items]`, where `offset` is drawn from on-chain
+		/// randomness once the sale has ended.
+		#[pallet::weight(
+			10_000 + T::DbWeight::get().reads_writes(2, 2 + 2 * metadata.len() as Weight)
+		)]
+		pub fn reveal(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			metadata: Vec<Vec<u8>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(who == collection.admin, Error::<T>::NoPermission);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+			let mut reveal = Self::reveals(collection_id).ok_or(<Error<T>>::NoProvenance)?;
+			ensure!(!reveal.revealed, Error::<T>::AlreadyRevealed);
+			ensure!(Self::sale_has_ended(&collection), Error::<T>::SaleNotEnded);
+			ensure!(
+				metadata.len() == collection.number_of_items as usize,
+				Error::<T>::MetadataLengthMismatch
+			);
+			ensure!(
+				T::Hashing::hash_of(&metadata) == reveal.provenance,
+				Error::<T>::InvalidProvenance
+			);
+
+			let offset = match reveal.offset {
+				Some(offset) => offset,
+				None => Self::draw_reveal_offset(collection_id, &collection),
+			};
+			for nft_id in 0..Self::next_nft_id(&collection) {
+				// Burned items keep no metadata
+				if !<NFTOwned<T>>::contains_key(collection_id, nft_id) {
+					continue
+				}
+				let index = (nft_id as u64 + offset as u64) % collection.number_of_items as u64;
+				let image_url: BoundedVec<u8, T::UriLimit> = metadata[index as usize]
+					.clone()
+					.try_into()
+					.map_err(|()| Error::<T>::UriTooLong)?;
+				<NFTs<T>>::mutate(collection_id, nft_id, |nft| nft.image_url = image_url.clone());
+				<NFTMap<T>>::mutate(collection_id, nft_id, |maybe_nft| {
+					if let Some(nft) = maybe_nft {
+						nft.image_url = image_url;
+					}
+				});
+			}
+
+			reveal.offset = Some(offset);
+			reveal.revealed = true;
+			<Reveals<T>>::insert(collection_id, reveal);
+
+			Self::deposit_event(Event::Revealed(collection_id));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().writes(1))]
+		pub fn dispense_fund(origin: OriginFor<T>, index: FundIndex, beneficiary: T::AccountId) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+
+			ensure!(who == fund.beneficiary, Error::<T>::NotFundOwner);
+
+			Self::dispense(index, beneficiary)?;
+
+			Self::deposit_event(Event::Dispensed(index, now, who));
+
+			Ok(().into())
+		}
+
+		/// Withdraw the caller's contribution from a fund that ended without reaching its goal. The
+		/// items the caller holds in the collection are burned.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			4 + T::MaxNFTOwned::get() as Weight,
+			4 + 8 * T::MaxNFTOwned::get() as Weight
+		))]
+		pub fn withdraw(origin: OriginFor<T>, index: FundIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::refund_contribution(index, &who)
+		}
+
+		/// Return the contribution of `contributor` from a fund that ended without reaching its
+		/// goal and burn the items they hold in the collection. Anyone may call this, e.g. the
+		/// beneficiary before dissolving the fund.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(
+			4 + T::MaxNFTOwned::get() as Weight,
+			4 + 8 * T::MaxNFTOwned::get() as Weight
+		))]
+		pub fn refund(
+			origin: OriginFor<T>,
+			index: FundIndex,
+			contributor: T::AccountId,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+
+			Self::refund_contribution(index, &contributor)
+		}
+
+		/// Reap a failed fund whose contributions have all been refunded and return the
+		/// `SubmissionDeposit` to the beneficiary.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 3))]
+		pub fn dissolve(origin: OriginFor<T>, index: FundIndex) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+
+			ensure!(who == fund.beneficiary, Error::<T>::NotFundOwner);
+			Self::ensure_fund_failed(&fund)?;
+			ensure!(fund.raised.is_zero(), Error::<T>::FundNotEmpty);
+
+			Self::close_fund(index, &fund);
+
+			Self::deposit_event(Event::Dissolved(index, now, who));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 4))]
+		pub fn transfer(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::do_transfer(collection_id, nft_id, &who, &to)
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn approve(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			delegate: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+			<NFTApprovals<T>>::insert(collection_id, nft_id, &delegate);
+
+			Self::deposit_event(Event::ApprovalGranted(collection_id, nft_id, who, delegate));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn cancel_approval(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+			ensure!(<NFTApprovals<T>>::contains_key(collection_id, nft_id), Error::<T>::NoApproval);
+			<NFTApprovals<T>>::remove(collection_id, nft_id);
+
+			Self::deposit_event(Event::ApprovalCancelled(collection_id, nft_id, who));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(3, 4))]
+		pub fn transfer_from(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			from: T::AccountId,
+			to: T::AccountId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let delegate =
+				<NFTApprovals<T>>::get(collection_id, nft_id).ok_or(<Error<T>>::NotApproved)?;
+			ensure!(who == delegate, Error::<T>::NotApproved);
+			ensure!(!<Listings<T>>::contains_key(collection_id, nft_id), Error::<T>::NFTListed);
+
+			Self::do_transfer(collection_id, nft_id, &from, &to)
+		}
+
+		/// Destroy an NFT. If its collection is redeemable, the owner is paid the fund balance
+		/// divided by the outstanding supply once the fund has succeeded.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 8))]
+		pub fn burn(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+
+			Self::do_burn(collection_id, nft_id, &who)
+		}
+
+		/// List an NFT for sale at a fixed `price`. Approved delegates can not move a listed NFT;
+		/// the listing is cleared when the owner transfers or burns it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(1, 1))]
+		pub fn list_for_sale(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+			Self::ensure_transferable(collection_id, nft_id)?;
+
+			<Listings<T>>::insert(collection_id, nft_id, Listing { seller: who.clone(), price });
+
+			Self::deposit_event(Event::Listed(collection_id, nft_id, who, price));
+			Ok(())
+		}
+
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(2, 1))]
+		pub fn cancel_listing(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::ensure_nft_owner(collection_id, nft_id, &who)?;
+			ensure!(<Listings<T>>::contains_key(collection_id, nft_id), Error::<T>::NotListed);
+
+			<Listings<T>>::remove(collection_id, nft_id);
+
+			Self::deposit_event(Event::ListingCancelled(collection_id, nft_id));
+			Ok(())
+		}
+
+		/// Buy a listed NFT. The collection royalty is deducted from the price and the rest is
+		/// paid to the seller.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(4, 6))]
+		#[transactional]
+		pub fn buy(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			max_price: BalanceOf<T>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let listing = <Listings<T>>::get(collection_id, nft_id).ok_or(<Error<T>>::NotListed)?;
+			ensure!(who != listing.seller, Error::<T>::BuyerIsSeller);
+			ensure!(listing.price <= max_price, Error::<T>::PriceTooHigh);
+
+			let royalty = Self::pay_royalty(collection_id, nft_id, &who, listing.price)?;
+			T::Currency::transfer(
+				&who,
+				&listing.seller,
+				listing.price.saturating_sub(royalty),
+				ExistenceRequirement::KeepAlive,
+			)?;
+			Self::do_transfer(collection_id, nft_id, &listing.seller, &who)?;
+
+			Self::deposit_event(Event::Sold(
+				collection_id,
+				nft_id,
+				listing.seller,
+				who,
+				listing.price,
+			));
+			Ok(())
+		}
+
+		/// Lock an item in its fraction account and mint `supply` shares of the new `T::Assets`
+		/// token `asset_id` to the caller. If `reserve_price` is set, anyone can buy the whole item
+		/// out for it.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 8))]
+		#[transactional]
+		pub fn fractionalize(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			asset_id: T::AssetId,
+			supply: BalanceOf<T>,
+			reserve_price: Option<BalanceOf<T>>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!supply.is_zero(), Error::<T>::InvalidSupply);
+
+			let account = Self::fraction_account_id(collection_id, nft_id);
+			Self::do_transfer(collection_id, nft_id, &who, &account)?;
+			T::Assets::create(asset_id, account, false, 1u32.into())?;
+			T::Assets::mint_into(asset_id, &who, supply)?;
+			<Fractions<T>>::insert(
+				collection_id,
+				nft_id,
+				FractionInfo { asset_id, supply, reserve_price, proceeds: None },
+			);
+
+			Self::deposit_event(Event::Fractionalized(
+				collection_id,
+				nft_id,
+				who,
+				asset_id,
+				supply,
+			));
+			Ok(())
+		}
+
+		/// Burn the full share supply of a fractionalized item to release it to the caller. The
+		/// share token is destroyed.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 8))]
+		#[transactional]
+		pub fn redeem(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let fraction =
+				Self::fractions(collection_id, nft_id).ok_or(<Error<T>>::NotFractionalized)?;
+			ensure!(fraction.proceeds.is_none(), Error::<T>::AlreadyBoughtOut);
+			ensure!(
+				T::Assets::balance(fraction.asset_id, &who) == fraction.supply,
+				Error::<T>::NotAllShares
+			);
+
+			T::Assets::burn_from(fraction.asset_id, &who, fraction.supply)?;
+			Self::destroy_shares(fraction.asset_id)?;
+			let account = Self::fraction_account_id(collection_id, nft_id);
+			Self::do_transfer(collection_id, nft_id, &account, &who)?;
+			<Fractions<T>>::remove(collection_id, nft_id);
+
+			Self::deposit_event(Event::FractionsRedeemed(collection_id, nft_id, who));
+			Ok(())
+		}
+
+		/// Buy a fractionalized item out at its reserve price. The collection royalty is deducted
+		/// from the price and the rest is held for the shareholders to claim.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(6, 7))]
+		#[transactional]
+		pub fn buyout(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut fraction =
+				Self::fractions(collection_id, nft_id).ok_or(<Error<T>>::NotFractionalized)?;
+			ensure!(fraction.proceeds.is_none(), Error::<T>::AlreadyBoughtOut);
+			let price = fraction.reserve_price.ok_or(<Error<T>>::NoReservePrice)?;
+
+			let royalty = Self::pay_royalty(collection_id, nft_id, &who, price)?;
+			let proceeds = price.saturating_sub(royalty);
+			let account = Self::fraction_account_id(collection_id, nft_id);
+			T::Currency::transfer(&who, &account, proceeds, ExistenceRequirement::KeepAlive)?;
+			Self::do_transfer(collection_id, nft_id, &account, &who)?;
+			fraction.proceeds = Some(proceeds);
+			<Fractions<T>>::insert(collection_id, nft_id, fraction);
+
+			Self::deposit_event(Event::BoughtOut(collection_id, nft_id, who, price));
+			Ok(())
+		}
+
+		/// Burn all shares of a bought out item held by the caller for their part of the price.
+		/// Every payout has to reach the existential deposit. The fraction account is kept alive
+		/// until the last shareholder claims, who also gets the rounding dust.
+		#[pallet::weight(10_000 + T::DbWeight::get().reads_writes(5, 5))]
+		#[transactional]
+		pub fn claim_buyout(
+			origin: OriginFor<T>,
+			collection_id: CollectionId,
+			nft_id: NFTId,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+
+			let mut fraction =
+				Self::fractions(collection_id, nft_id).ok_or(<Error<T>>::NotFractionalized)?;
+			let proceeds = fraction.proceeds.ok_or(<Error<T>>::NotBoughtOut)?;
+			let shares = T::Assets::balance(fraction.asset_id, &who);
+			ensure!(!shares.is_zero(), Error::<T>::NoShares);
+
+			// The last shareholder gets the rest, so no rounding dust is left behind. Everyone else
+			// leaves at least the existential deposit for them.
+			let outstanding = T::Assets::total_issuance(fraction.asset_id);
+			let is_last = shares >= outstanding;
+			let minimum_balance = T::Currency::minimum_balance();
+			let (amount, existence) = if is_last {
+				(proceeds, ExistenceRequirement::AllowDeath)
+			} else {
+				let amount = Perquintill::from_rational(shares, outstanding).mul_floor(proceeds);
+				(
+					amount.min(proceeds.saturating_sub(minimum_balance)),
+					ExistenceRequirement::KeepAlive,
+				)
+			};
+			ensure!(amount >= minimum_balance, Error::<T>::PayoutTooSmall);
+			T::Assets::burn_from(fraction.asset_id, &who, shares)?;
+			T::Currency::transfer(
+				&Self::fraction_account_id(collection_id, nft_id),
+				&who,
+				amount,
+				existence,
+			)?;
+			if is_last {
+				Self::destroy_shares(fraction.asset_id)?;
+				<Fractions<T>>::remove(collection_id, nft_id);
+			} else {
+				fraction.proceeds = Some(proceeds.saturating_sub(amount));
+				<Fractions<T>>::insert(collection_id, nft_id, fraction);
+			}
+
+			Self::deposit_event(Event::BuyoutClaimed(collection_id, nft_id, who, shares, amount));
+			Ok(())
+		}
+	}
+
+	impl<T: Config> Pallet<T> {
+		pub fn get_launchpad_collections() -> u32 {
+			10
+		}
+
+		/// Validate `config` and store the collection and fund it describes.
+		fn new_collection(
+			owner: &T::AccountId,
+			config: CollectionConfigOf<T>,
+		) -> Result<CollectionId, DispatchError> {
+			let CollectionConfig {
+				name,
+				description,
+				base_uri,
+				number_of_items,
+				mint_fee,
+				goal,
+				fund_end,
+				redeemable,
+				royalty,
+				sale_mode,
+				max_per_account,
+				payment_asset,
+				soulbound,
+				milestones,
+			} = config;
+
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(fund_end > now, Error::<T>::FundEndTooEarly);
+
+			let name: BoundedVec<u8, T::NameLimit> =
+				name.try_into().map_err(|()| Error::<T>::NameTooLong)?;
+			let description: BoundedVec<u8, T::DescriptionLimit> =
+				description.try_into().map_err(|()| Error::<T>::DescriptionTooLong)?;
+			Self::ensure_uri_room(&base_uri, number_of_items)?;
+			let base_uri: BoundedVec<u8, T::UriLimit> =
+				base_uri.try_into().map_err(|()| Error::<T>::UriTooLong)?;
+			match sale_mode {
+				SaleMode::FixedPrice => {},
+				SaleMode::DutchAuction { start_price, floor_price, .. } =>
+					ensure!(floor_price <= start_price, Error::<T>::InvalidSaleMode),
+				// Bids are reserved in `T::Currency`
+				SaleMode::EnglishAuction { .. } => ensure!(
+					number_of_items == 1 && payment_asset == PaymentAsset::Native,
+					Error::<T>::InvalidSaleMode
+				),
+			}
+			let milestones: BoundedVec<MilestoneOf<T>, T::MaxMilestones> =
+				milestones.try_into().map_err(|_| Error::<T>::TooManyMilestones)?;
+			if !milestones.is_empty() {
+				// Redeemable items would pay out the funds the milestones hold back.
+				ensure!(!redeemable, Error::<T>::InvalidMilestones);
+				let total = milestones
+					.iter()
+					.fold(BalanceOf::<T>::zero(), |total, m| total.saturating_add(m.amount));
+				ensure!(total == goal, Error::<T>::InvalidMilestones);
+				let mut previous = fund_end;
+				for milestone in milestones.iter() {
+					ensure!(milestone.deadline > previous, Error::<T>::InvalidMilestones);
+					previous = milestone.deadline;
+				}
+			}
+
+			let slug = Self::slugify(&name);
+			ensure!(!slug.is_empty(), Error::<T>::InvalidCollectionName);
+			let slug_hash = T::Hashing::hash_of(&slug);
+			ensure!(
+				!<CollectionByName<T>>::contains_key(slug_hash),
+				Error::<T>::CollectionNameTaken
+			);
+			// A slug is never longer than the name it is built from.
+			let slug: BoundedVec<u8, T::NameLimit> =
+				slug.try_into().map_err(|_| Error::<T>::NameTooLong)?;
+
+			let collection_id = <FundCount<T>>::get();
+			let next_id = collection_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			<FundCount<T>>::put(next_id);
+
+			let collection_info = CollectionInfo::<T> {
+				id: collection_id,
+				owner: owner.clone(),
+				name,
+				description,
+				base_uri,
+				number_of_items,
+				project_status: ProjectStatus::Draft,
+				is_frozen: false,
+				number_of_minted: 0,
+				mint_fee,
+				start_date: None,
+				end_date: None,
+				sale_window_unit: SaleWindowUnit::BlockNumber,
+				redeemable,
+				number_of_burned: 0,
+				royalty,
+				sale_mode,
+				sale_phase: SalePhase::Public,
+				presale: None,
+				max_per_account,
+				soulbound,
+				issuer: owner.clone(),
+				admin: owner.clone(),
+				freezer: owner.clone(),
+				slug,
+			};
+
+			// Check if the collection id does not already exist in our storage map
+			ensure!(Self::get_collections(&collection_id) == None, <Error<T>>::CollectionExists);
+
+			log::info!("A collection is created with ID: {:?}", collection_id);
+
+			// Save collection on-chain
+			Collections::<T>::insert(collection_id, collection_info);
+			<CollectionByName<T>>::insert(slug_hash, collection_id);
+
+			// Create Fund
+			Self::create_fund(&owner, collection_id, goal, fund_end, payment_asset)
+				.map_err(|e| e.error)?;
+			if !milestones.is_empty() {
+				<Milestones<T>>::insert(collection_id, milestones);
+			}
+
+			Ok(collection_id)
+		}
+
+		/// Normalize a collection name into its slug. ASCII letters are lowercased, letters, digits
+		/// and non-ASCII characters are kept and every other run of characters becomes a single
+		/// `-`. Non-ASCII characters are kept as they are, so "Café" becomes "café" and "CAFÉ"
+		/// becomes "cafÉ".
+		pub fn slugify(name: &[u8]) -> Vec<u8> {
+			let mut slug = Vec::with_capacity(name.len());
+			for &byte in name {
+				if byte.is_ascii_alphanumeric() || !byte.is_ascii() {
+					slug.push(byte.to_ascii_lowercase());
+				} else if slug.last().map_or(false, |last| *last != b'-') {
+					slug.push(b'-');
+				}
+			}
+			if slug.last() == Some(&b'-') {
+				slug.pop();
+			}
+			slug
+		}
+
+		/// The collection whose name normalizes to the same slug as `slug`.
+		pub fn collection_by_slug(slug: Vec<u8>) -> Option<CollectionId> {
+			<CollectionByName<T>>::get(T::Hashing::hash_of(&Self::slugify(&slug)))
+		}
+
+		/// The royalty recipient and amount owed on a sale of `nft_id` for `sale_price`. Royalties
+		/// are configured per collection, so every item of a collection pays the same rate.
+		pub fn royalty_info(
+			collection_id: CollectionId,
+			_nft_id: NFTId,
+			sale_price: BalanceOf<T>,
+		) -> Option<(T::AccountId, BalanceOf<T>)> {
+			let royalty = Self::get_collections(&collection_id)?.royalty?;
+			Some((royalty.recipient, royalty.rate.mul_floor(sale_price)))
+		}
+
+		/// Charge the royalty on a sale of `nft_id` for `sale_price` to `buyer`. Every in-pallet
+		/// sale path must call this. Returns the amount paid.
+		pub fn pay_royalty(
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			buyer: &T::AccountId,
+			sale_price: BalanceOf<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			let (recipient, amount) = match Self::royalty_info(collection_id, nft_id, sale_price) {
+				Some((recipient, amount)) if !amount.is_zero() => (recipient, amount),
+				_ => return Ok(Zero::zero()),
+			};
+			if &recipient != buyer {
+				T::Currency::transfer(buyer, &recipient, amount, ExistenceRequirement::KeepAlive)?;
+			}
+
+			Self::deposit_event(Event::RoyaltyPaid(collection_id, nft_id, recipient, amount));
+			Ok(amount)
+		}
+
+		/// The price of the next item minted from a collection in its current sale mode.
+		fn mint_price(
+			collection_id: CollectionId,
+			collection: &CollectionInfo<T>,
+		) -> Result<BalanceOf<T>, DispatchError> {
+			match collection.sale_mode {
+				SaleMode::FixedPrice => Ok(collection.mint_fee),
+				SaleMode::DutchAuction { start_price, floor_price, decay } => {
+					let now = <frame_system::Pallet<T>>::block_number();
+					let opened = Self::sale_opened_at(collection_id).unwrap_or(now);
+					let elapsed: u32 = now.saturating_sub(opened).unique_saturated_into();
+					let discount = decay.saturating_mul(elapsed.into());
+					Ok(start_price.saturating_sub(discount).max(floor_price))
+				},
+				SaleMode::EnglishAuction { .. } => Err(Error::<T>::AuctionOnly.into()),
+			}
+		}
+
+		/// Mint the next item of a collection to `who` and store the updated collection.
+		fn insert_nft(
+			collection_id: CollectionId,
+			collection: &mut CollectionInfo<T>,
+			who: &T::AccountId,
+		) -> Result<NFTId, DispatchError> {
+			let minted = Self::minted_per_account(collection_id, who).saturating_add(1);
+			if let Some(max_per_account) = collection.max_per_account {
+				ensure!(minted <= max_per_account, Error::<T>::MintLimitReached);
+			}
+			<MintedPerAccount<T>>::insert(collection_id, who, minted);
+
+			let nft = Self::generate_collection_nft(
+				collection_id,
+				collection,
+				Self::next_nft_id(collection),
+			)?;
+			if let Some((_, Some((depositor, deposit)))) =
+				<ItemMetadata<T>>::take(collection_id, nft.id)
+			{
+				T::Currency::unreserve(&depositor, deposit);
+			}
+			// Store data on chain
+			let nft_id = nft.id;
+			NFTs::<T>::insert(collection_id, nft_id, &nft);
+			log::info!("A NFT is minted with ID: {:?} in collection id: {:?}", nft_id, collection_id);
+
+			collection.number_of_minted =
+				collection.number_of_minted.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+			<Collections<T>>::insert(&collection_id, &*collection);
+
+			<NFTOwned<T>>::insert(collection_id, nft_id, who);
+			<NFTMap<T>>::insert(collection_id, nft_id, &nft);
+			<CollectionOwnerNFT<T>>::try_mutate(collection_id, who, |nft_vec| {
+				nft_vec.try_push(nft_id)
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxNFTOwned)?;
+			Ok(nft_id)
+		}
+
+		/// Mint the item of a closed English auction to the highest bidder and route the bid into
+		/// the collection fund. If that fails the bid is refunded.
+		fn settle_auction(collection_id: CollectionId) {
+			let (winner, amount) = match <Bids<T>>::take(collection_id) {
+				Some(bid) => bid,
+				None => return,
+			};
+			T::Currency::unreserve(&winner, amount);
+
+			let settled = with_transaction(|| {
+				let result = Self::get_collections(&collection_id)
+					.ok_or(DispatchError::from(Error::<T>::CollectionNotExists))
+					.and_then(|mut collection| {
+						let nft_id = Self::insert_nft(collection_id, &mut collection, &winner)?;
+						Self::contribute(&winner, collection_id, amount).map_err(|e| e.error)?;
+						Ok(nft_id)
+					});
+				match result {
+					Ok(nft_id) => TransactionOutcome::Commit(Ok(nft_id)),
+					Err(e) => TransactionOutcome::Rollback(Err::<NFTId, DispatchError>(e)),
+				}
+			});
+
+			match settled {
+				Ok(nft_id) =>
+					Self::deposit_event(Event::AuctionSettled(collection_id, nft_id, winner, amount)),
+				Err(_) => Self::deposit_event(Event::AuctionRefunded(collection_id, winner, amount)),
+			}
+		}
+
+		/// Fold a Merkle proof into the root of the tree containing `leaf`. Each pair of nodes is
+		/// hashed in ascending order, so the proof does not need to encode positions.
+		pub fn merkle_root_of(leaf: T::Hash, proof: &[T::Hash]) -> T::Hash {
+			proof.iter().fold(leaf, |node, sibling| {
+				if node <= *sibling {
+					T::Hashing::hash_of(&(node, sibling))
+				} else {
+					T::Hashing::hash_of(&(sibling, node))
+				}
+			})
+		}
+
+		/// Id of the next item to mint. Ids of burned items are never reused.
+		fn next_nft_id(collection: &CollectionInfo<T>) -> NFTId {
+			collection.number_of_minted.saturating_add(collection.number_of_burned)
+		}
+
+		/// Build the NFT minted as item `nft_id` of a collection. Its image URL is the uploaded item
+		/// metadata if there is any, otherwise the collection base URI followed by the item id.
+		fn generate_collection_nft(
+			collection_id: CollectionId,
+			collection: &CollectionInfo<T>,
+			nft_id: NFTId,
+		) -> Result<NFT<T>, DispatchError> {
+			let image_url = match <ItemMetadata<T>>::get(collection_id, nft_id) {
+				// Items of a delayed reveal collection point to the collection metadata until the
+				// reveal.
+				_ if <Reveals<T>>::contains_key(collection_id) => collection.base_uri.clone(),
+				Some((uri, _)) => uri,
+				None => {
+					let mut uri = collection.base_uri.to_vec();
+					uri.extend_from_slice(format!("{}", nft_id).as_bytes());
+					uri.try_into().map_err(|()| Error::<T>::UriTooLong)?
+				},
+			};
+			let name = format!("Item #{}", nft_id)
+				.as_bytes()
+				.to_vec()
+				.try_into()
+				.map_err(|()| Error::<T>::NameTooLong)?;
+
+			Ok(NFT { id: nft_id, name, image_url })
+		}
+
+		/// Ensure the item URIs derived from `base_uri` fit in `UriLimit`, including that of the
+		/// last of `number_of_items` items, which has the longest id.
+		fn ensure_uri_room(base_uri: &[u8], number_of_items: u32) -> DispatchResult {
+			let id_len = format!("{}", number_of_items.saturating_sub(1)).len();
+			ensure!(
+				base_uri.len().saturating_add(id_len) <= T::UriLimit::get() as usize,
+				Error::<T>::UriTooLong
+			);
+			Ok(())
+		}
+
+		/// The current point in time in the given sale window unit.
+		fn sale_window_now(unit: SaleWindowUnit) -> u64 {
+			match unit {
+				SaleWindowUnit::BlockNumber =>
+					<frame_system::Pallet<T>>::block_number().unique_saturated_into(),
+				SaleWindowUnit::Moment => T::Timestamp::now().unique_saturated_into(),
+			}
+		}
+
+		pub fn ensure_sale_open(collection: &CollectionInfo<T>) -> DispatchResult {
+			let now = Self::sale_window_now(collection.sale_window_unit);
+			if let Some(start_date) = collection.start_date {
+				ensure!(now >= start_date, Error::<T>::SaleNotStarted);
+			}
+			if let Some(end_date) = collection.end_date {
+				ensure!(now < end_date, Error::<T>::SaleEnded);
+			}
+			Ok(())
+		}
+
+		pub fn sale_has_ended(collection: &CollectionInfo<T>) -> bool {
+			let now = Self::sale_window_now(collection.sale_window_unit);
+			collection.end_date.map_or(false, |end_date| now >= end_date)
+		}
+
+		/// Draw and store the reveal offset of a delayed reveal collection from on-chain randomness.
+		/// Must only be called once the sale has ended, so minters can not predict their items.
+		fn draw_reveal_offset(collection_id: CollectionId, collection: &CollectionInfo<T>) -> u32 {
+			let (seed, _) =
+				T::CollectionRandomness::random(&(b"NFT Reveal", collection_id).encode());
+			let random = u32::decode(&mut TrailingZeroInput::new(seed.as_ref()))
+				.expect("input is padded with zeroes; qed");
+			let offset = random.checked_rem(collection.number_of_items).unwrap_or_default();
+
+			<Reveals<T>>::mutate(collection_id, |maybe_reveal| {
+				if let Some(reveal) = maybe_reveal {
+					reveal.offset = Some(offset);
+				}
+			});
+			Self::deposit_event(Event::RevealOffsetDrawn(collection_id, offset));
+			offset
+		}
+
+		/// Emit an event for every sale window that opened or closed since the last block and drop
+		/// closed windows from `SaleWindows`.
+		fn update_sale_windows() -> Weight {
+			let windows = <SaleWindows<T>>::get();
+			let mut reads: Weight = 1;
+			let mut writes: Weight = 0;
+			let mut changed = false;
+
+			let remaining: Vec<(CollectionId, bool)> = windows
+				.into_inner()
+				.into_iter()
+				.filter_map(|(collection_id, opened)| {
+					reads += 1;
+					let collection = match Self::get_collections(&collection_id) {
+						Some(collection) => collection,
+						None => {
+							changed = true;
+							return None
+						},
+					};
+					let now = Self::sale_window_now(collection.sale_window_unit);
+
+					if collection.end_date.map_or(false, |end_date| now >= end_date) {
+						Self::deposit_event(Event::SaleWindowClosed(collection_id));
+						<SaleOpenedAt<T>>::remove(collection_id);
+						writes += 1;
+						if let SaleMode::EnglishAuction { .. } = collection.sale_mode {
+							// Bounded by the number of sale windows closing in this block
+							let _ = <AuctionsToSettle<T>>::try_append(collection_id);
+							// Settlement mints one item and contributes the winning bid
+							reads += 4;
+							writes += 10;
+						}
+						reads += 1;
+						if <Reveals<T>>::get(collection_id).map_or(false, |r| r.offset.is_none()) {
+							Self::draw_reveal_offset(collection_id, &collection);
+							writes += 1;
+						}
+						changed = true;
+						return None
+					}
+					if !opened && collection.start_date.map_or(true, |start_date| now >= start_date)
+					{
+						Self::deposit_event(Event::SaleWindowOpened(collection_id));
+						<SaleOpenedAt<T>>::insert(
+							collection_id,
+							<frame_system::Pallet<T>>::block_number(),
+						);
+						writes += 1;
+						changed = true;
+						return Some((collection_id, true))
+					}
+					Some((collection_id, opened))
+				})
+				.collect();
+
+			if !changed {
+				return T::DbWeight::get().reads(reads)
+			}
+			// The list only ever shrinks here, so it always fits the bound.
+			if let Ok(remaining) = BoundedVec::<_, T::MaxActiveSaleWindows>::try_from(remaining) {
+				<SaleWindows<T>>::put(remaining);
+			}
+			T::DbWeight::get().reads_writes(reads, writes + 1)
+		}
+
+		fn ensure_nft_owner(
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			let owner =
+				<NFTOwned<T>>::get(collection_id, nft_id).ok_or(<Error<T>>::NFTNotExists)?;
+			ensure!(owner == *who, Error::<T>::NotNFTOwner);
+			Ok(())
+		}
+
+		/// Move `nft_id` of `collection_id` from `from` to `to`, keeping the ownership indexes in
+		/// sync and clearing any pending approval.
+		pub fn do_transfer(
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			from: &T::AccountId,
+			to: &T::AccountId,
+		) -> DispatchResult {
+			Self::ensure_nft_owner(collection_id, nft_id, from)?;
+			ensure!(from != to, Error::<T>::TransferToSelf);
+			Self::ensure_transferable(collection_id, nft_id)?;
+
+			<CollectionOwnerNFT<T>>::try_mutate(collection_id, to, |nft_vec| {
+				nft_vec.try_push(nft_id)
+			})
+			.map_err(|_| <Error<T>>::ExceedMaxNFTOwned)?;
+			<CollectionOwnerNFT<T>>::mutate(collection_id, from, |nft_vec| {
+				nft_vec.retain(|id| *id != nft_id)
+			});
+
+			<NFTOwned<T>>::insert(collection_id, nft_id, to);
+			<NFTApprovals<T>>::remove(collection_id, nft_id);
+			<Listings<T>>::remove(collection_id, nft_id);
+
+			Self::deposit_event(Event::Transferred(collection_id, nft_id, from.clone(), to.clone()));
+			Ok(())
+		}
+
+		/// Destroy `nft_id` of `collection_id` owned by `who`, paying out its share of the fund if
+		/// the collection is redeemable.
+		pub fn do_burn(
+			collection_id: CollectionId,
+			nft_id: NFTId,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			let mut collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			// The shareholders own a fractionalized item.
+			ensure!(
+				!<Fractions<T>>::contains_key(collection_id, nft_id),
+				Error::<T>::Fractionalized
+			);
+
+			let mut redeemed = Zero::zero();
+			if collection.redeemable {
+				if let Some(mut fund) = Self::funds(collection_id) {
+					let now = <frame_system::Pallet<T>>::block_number();
+					ensure!(now > fund.end, Error::<T>::FundStillActive);
+					ensure!(fund.raised >= fund.goal, Error::<T>::FundFailed);
+
+					let outstanding: BalanceOf<T> = collection.number_of_minted.into();
+					redeemed = fund.raised / outstanding;
+					Self::pay_out(collection_id, &fund, who, redeemed)?;
+					fund.raised = fund.raised.saturating_sub(redeemed);
+					<Funds<T>>::insert(collection_id, &fund);
+				}
+			}
+
+			Self::remove_nft(collection_id, &mut collection, nft_id, who);
+			<Collections<T>>::insert(&collection_id, collection);
+
+			Self::deposit_event(Event::Burned(collection_id, nft_id, who.clone(), redeemed));
+			Ok(())
+		}
+
+		/// Remove item `nft_id` of `who` and everything stored about it, counting it as burned.
+		/// The caller stores `collection`.
+		fn remove_nft(
+			collection_id: CollectionId,
+			collection: &mut CollectionInfo<T>,
+			nft_id: NFTId,
+			who: &T::AccountId,
+		) {
+			collection.number_of_minted = collection.number_of_minted.saturating_sub(1);
+			collection.number_of_burned = collection.number_of_burned.saturating_add(1);
+
+			<CollectionOwnerNFT<T>>::mutate(collection_id, who, |nft_vec| {
+				nft_vec.retain(|id| *id != nft_id)
+			});
+			<NFTOwned<T>>::remove(collection_id, nft_id);
+			<NFTApprovals<T>>::remove(collection_id, nft_id);
+			<Listings<T>>::remove(collection_id, nft_id);
+			<FrozenItems<T>>::remove(collection_id, nft_id);
+			<NFTMap<T>>::remove(collection_id, nft_id);
+			<NFTs<T>>::remove(collection_id, nft_id);
+			Self::clear_attributes(collection_id, Some(nft_id));
+		}
+
+		/// Store an attribute of a collection, or of one of its items if `nft_id` is set. The
+		/// attribute deposit is reserved from `maybe_depositor`, if any, and the deposit of the
+		/// previous value is unreserved.
+		pub fn do_set_attribute(
+			collection_id: CollectionId,
+			nft_id: Option<NFTId>,
+			key: &[u8],
+			value: &[u8],
+			maybe_depositor: Option<&T::AccountId>,
+		) -> DispatchResult {
+			ensure!(<Collections<T>>::contains_key(collection_id), Error::<T>::CollectionNotExists);
+			let key: BoundedVec<u8, T::KeyLimit> =
+				key.to_vec().try_into().map_err(|()| Error::<T>::KeyTooLong)?;
+			let value: BoundedVec<u8, T::ValueLimit> =
+				value.to_vec().try_into().map_err(|()| Error::<T>::ValueTooLong)?;
+
+			let deposit = match maybe_depositor {
+				Some(depositor) => {
+					let bytes = (key.len() + value.len()) as u32;
+					let amount = T::DepositPerByte::get()
+						.saturating_mul(bytes.into())
+						.saturating_add(T::AttributeDepositBase::get());
+					T::Currency::reserve(depositor, amount)?;
+					Some((depositor.clone(), amount))
+				},
+				None => None,
+			};
+			if let Some((_, Some((old_depositor, old_amount)))) =
+				<Attributes<T>>::get((collection_id, nft_id, &key))
+			{
+				T::Currency::unreserve(&old_depositor, old_amount);
+			}
+
+			<Attributes<T>>::insert((collection_id, nft_id, &key), (value.clone(), deposit));
+			Self::deposit_event(Event::AttributeSet(collection_id, nft_id, key, value));
+			Ok(())
+		}
+
+		/// Remove the attributes of a collection, or only of one of its items if `nft_id` is set,
+		/// and unreserve their deposits.
+		fn clear_attributes(collection_id: CollectionId, nft_id: Option<NFTId>) {
+			let attributes: Vec<_> = match nft_id {
+				Some(nft_id) => <Attributes<T>>::drain_prefix((collection_id, Some(nft_id)))
+					.map(|(_, attribute)| attribute)
+					.collect(),
+				None => <Attributes<T>>::drain_prefix((collection_id,))
+					.map(|(_, attribute)| attribute)
+					.collect(),
+			};
+			for (_, deposit) in attributes {
+				if let Some((depositor, amount)) = deposit {
+					T::Currency::unreserve(&depositor, amount);
+				}
+			}
+		}
+
+		/// The collection, current milestone and milestone progress of a collection whose fund has
+		/// succeeded and still has milestones to release.
+		fn current_milestone(
+			collection_id: CollectionId,
+		) -> Result<
+			(CollectionInfo<T>, MilestoneOf<T>, MilestoneProgress<BalanceOf<T>>),
+			DispatchError,
+		> {
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			let fund = Self::funds(collection_id).ok_or(Error::<T>::NoMilestone)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > fund.end, Error::<T>::FundStillActive);
+			ensure!(fund.raised >= fund.goal, Error::<T>::GoalNotReached);
+
+			let progress = Self::milestone_progress(collection_id);
+			let milestone = Self::milestones(collection_id)
+				.get(progress.current as usize)
+				.cloned()
+				.ok_or(Error::<T>::NoMilestone)?;
+			Ok((collection, milestone, progress))
+		}
+
+		/// Whether a majority of the items rejected the current milestone, it missed its deadline
+		/// or refunds of it already started.
+		fn milestone_has_failed(
+			collection: &CollectionInfo<T>,
+			milestone: &MilestoneOf<T>,
+			progress: &MilestoneProgress<BalanceOf<T>>,
+		) -> bool {
+			let now = <frame_system::Pallet<T>>::block_number();
+			progress.refund_per_item.is_some() ||
+				progress.nays.saturating_mul(2) > collection.number_of_minted ||
+				now > milestone.deadline
+		}
+
+		/// The witness `cancel_collection` requires to cancel a collection.
+		pub fn cancel_witness(collection_id: CollectionId) -> Option<CancelWitness> {
+			Self::get_collections(&collection_id).map(|collection| CancelWitness {
+				items: collection.number_of_minted,
+				item_metadata: <ItemMetadata<T>>::iter_prefix(collection_id).count() as u32,
+				attributes: <Attributes<T>>::iter_prefix((collection_id,)).count() as u32,
+			})
+		}
+
+		/// Remove a collection, its items and everything stored about them. Attribute and item
+		/// metadata deposits are unreserved; the collection fund is left to the caller.
+		fn remove_collection(collection_id: CollectionId) {
+			if let Some(collection) = <Collections<T>>::take(collection_id) {
+				Self::release_name(collection_id, &collection);
+			}
+			let _ = <NFTs<T>>::remove_prefix(collection_id, None);
+			let _ = <NFTMap<T>>::remove_prefix(collection_id, None);
+			let _ = <NFTOwned<T>>::remove_prefix(collection_id, None);
+			let _ = <CollectionOwnerNFT<T>>::remove_prefix(collection_id, None);
+			for (_, (_, deposit)) in <ItemMetadata<T>>::drain_prefix(collection_id) {
+				if let Some((depositor, amount)) = deposit {
+					T::Currency::unreserve(&depositor, amount);
+				}
+			}
+			let _ = <NFTApprovals<T>>::remove_prefix(collection_id, None);
+			let _ = <Listings<T>>::remove_prefix(collection_id, None);
+			let _ = <AllowlistClaimed<T>>::remove_prefix(collection_id, None);
+			let _ = <MintedPerAccount<T>>::remove_prefix(collection_id, None);
+			let _ = <FrozenItems<T>>::remove_prefix(collection_id, None);
+			Self::clear_attributes(collection_id, None);
+			<Reveals<T>>::remove(collection_id);
+			<SaleOpenedAt<T>>::remove(collection_id);
+			<OwnershipAcceptance<T>>::remove(collection_id);
+			<RejectionReasons<T>>::remove(collection_id);
+			<Milestones<T>>::remove(collection_id);
+			<CurrentMilestone<T>>::remove(collection_id);
+			let _ = <MilestoneVotes<T>>::remove_prefix((collection_id,), None);
+			let _ = <MilestoneRefunded<T>>::remove_prefix(collection_id, None);
+			<SaleWindows<T>>::mutate(|windows| windows.retain(|(id, _)| *id != collection_id));
+		}
+
+		/// Free the name of a collection. Collections migrated with a duplicate name are not
+		/// indexed, so the name is only freed if it is indexed under this collection.
+		fn release_name(collection_id: CollectionId, collection: &CollectionInfo<T>) {
+			let slug_hash = T::Hashing::hash_of(&collection.slug);
+			if <CollectionByName<T>>::get(slug_hash) == Some(collection_id) {
+				<CollectionByName<T>>::remove(slug_hash);
+			}
+		}
+
+		/// Ensure an item is not frozen and not part of a frozen or soulbound collection.
+		pub fn ensure_transferable(collection_id: CollectionId, nft_id: NFTId) -> DispatchResult {
+			let collection =
+				Self::get_collections(&collection_id).ok_or(<Error<T>>::CollectionNotExists)?;
+			ensure!(!collection.soulbound, Error::<T>::Soulbound);
+			ensure!(!collection.is_frozen, Error::<T>::CollectionFrozen);
+			ensure!(!Self::is_item_frozen(collection_id, nft_id), Error::<T>::ItemFrozen);
+			Ok(())
 		}
 
 		pub fn fund_account_id(index: FundIndex) -> T::AccountId {
 			PALLET_ID.into_sub_account(index)
 		}
+
+		/// The account a fractionalized item is locked in. It also holds the buyout price until
+		/// the shareholders claim it.
+		pub fn fraction_account_id(collection_id: CollectionId, nft_id: NFTId) -> T::AccountId {
+			PALLET_ID.into_sub_account((b"frac", collection_id, nft_id))
+		}
+
+		/// Destroy the share token of a fractionalized item once all shares are burned, freeing
+		/// its asset id.
+		fn destroy_shares(asset_id: T::AssetId) -> DispatchResult {
+			let witness =
+				T::Assets::get_destroy_witness(&asset_id).ok_or(Error::<T>::NotFractionalized)?;
+			T::Assets::destroy(asset_id, witness, None)?;
+			Ok(())
+		}
 	}
 
 	impl<T: Config> Pallet<T> {
@@ -421,30 +2900,36 @@ pub mod pallet {
 		pub fn create_fund(
 			owner: &T::AccountId,
 			fund_index: FundIndex,
+			goal: BalanceOf<T>,
 			end: T::BlockNumber,
+			asset: PaymentAsset<T::AssetId>,
 		) -> DispatchResultWithPostInfo {
-			let deposit = T::SubmissionDeposit::get();
-			let imb = T::Currency::withdraw(
-				&owner,
-				deposit,
-				WithdrawReasons::TRANSFER,
-				ExistenceRequirement::AllowDeath,
+			// The first existential deposit of the submission deposit endows the fund account,
+			// which could otherwise not receive contributions below it and would be reaped by
+			// withdrawals leaving less.
+			let endowment = T::Currency::minimum_balance();
+			T::Currency::transfer(
+				owner,
+				&Self::fund_account_id(fund_index),
+				endowment,
+				ExistenceRequirement::KeepAlive,
 			)?;
-
-			// No fees are paid here if we need to create this account; that's why we don't just
-			// use the stock `transfer`.
-			let fund_account_id = Self::fund_account_id(fund_index);
-			let result = T::Currency::resolve_creating(&fund_account_id, imb);
-
-			log::info!(
-				"Creating funding pot result: {:?} with account id: {:?}",
-				result,
-				fund_account_id
-			);
+			// The rest is reserved from the owner, so it never mixes with the contributions
+			// held by the fund account.
+			let deposit = T::SubmissionDeposit::get().saturating_sub(endowment);
+			T::Currency::reserve(owner, deposit)?;
 
 			<Funds<T>>::insert(
 				fund_index,
-				FundInfo { beneficiary: owner.clone(), deposit, raised: Zero::zero(), end },
+				FundInfo {
+					beneficiary: owner.clone(),
+					deposit,
+					raised: Zero::zero(),
+					end,
+					goal,
+					asset,
+					fees: Zero::zero(),
+				},
 			);
 			log::info!("A fund spot is created: {:?}", fund_index);
 			Ok(().into())
@@ -459,8 +2944,33 @@ pub mod pallet {
 			let fund_account_id = Self::fund_account_id(index);
 
 			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(fund.end >= now, Error::<T>::ContributionPeriodOver);
+
+			// The protocol fee goes to `FeeHandler` or `AssetFeeHandler`, the rest is added to the
+			// fund and is refundable if the fund fails. Fees are withdrawn as an imbalance, so fees
+			// below the existential deposit or minimum balance do not depend on the receiving
+			// account.
+			let fee = T::ProtocolFee::get().mul_floor(value);
+			let value = value.saturating_sub(fee);
+			if !fee.is_zero() {
+				match fund.asset {
+					PaymentAsset::Native => T::FeeHandler::on_unbalanced(T::Currency::withdraw(
+						contributor,
+						fee,
+						WithdrawReasons::TRANSFER,
+						ExistenceRequirement::AllowDeath,
+					)?),
+					PaymentAsset::Asset(asset_id) => T::AssetFeeHandler::on_unbalanced(
+						T::Assets::withdraw(asset_id, contributor, fee)?,
+					),
+				}
+				fund.fees = fund.fees.saturating_add(fee);
+				Self::deposit_event(Event::ProtocolFeePaid(contributor.clone(), index, fee));
+			}
+
 			// Add contribution to the fund
-			let _ = T::Currency::transfer(
+			Self::transfer_fund_asset(
+				&fund,
 				&contributor,
 				&fund_account_id,
 				value,
@@ -468,49 +2978,454 @@ pub mod pallet {
 			)?;
 
 			fund.raised += value;
+			let balance = Self::contribution_get(index, contributor);
+			Self::contribution_put(index, contributor, &(balance + value));
 			Self::deposit_event(Event::Contributed(contributor.clone(), index, value, now));
 
-			// Check account id total balance
-			let current_funding = T::Currency::total_balance(&fund_account_id);
-			log::info!("current_funding: {:?}", current_funding);
+			log::info!("current_funding: {:?}", fund.raised);
 			Funds::<T>::insert(index, &fund);
 			Ok(().into())
 		}
 
 		pub fn dispense(index: FundIndex, beneficiary: T::AccountId) -> DispatchResultWithPostInfo {
 			let fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > fund.end, Error::<T>::FundStillActive);
+			ensure!(fund.raised >= fund.goal, Error::<T>::GoalNotReached);
+			ensure!(Self::milestones(index).is_empty(), Error::<T>::FundHasMilestones);
+			ensure!(
+				!Self::get_collections(index).map_or(false, |collection| collection.redeemable),
+				Error::<T>::FundRedeemable
+			);
 			let account = Self::fund_account_id(index);
-			let result = T::Currency::resolve_creating(
-				&beneficiary,
-				T::Currency::withdraw(
-					&account,
-					fund.raised,
-					WithdrawReasons::TRANSFER,
-					ExistenceRequirement::AllowDeath,
-				)?,
-			);
-			log::info!("Dispense result: {:?}", result);
-
-			// Remove the fund info from storage
-			<Funds<T>>::remove(index);
+			match fund.asset {
+				PaymentAsset::Native => {
+					let result = T::Currency::resolve_creating(
+						&beneficiary,
+						T::Currency::withdraw(
+							&account,
+							fund.raised,
+							WithdrawReasons::TRANSFER,
+							ExistenceRequirement::AllowDeath,
+						)?,
+					);
+					log::info!("Dispense result: {:?}", result);
+				},
+				PaymentAsset::Asset(_) => Self::pay_out(index, &fund, &beneficiary, fund.raised)?,
+			}
+			// Nothing is left reserved once the campaign completes.
+			Self::close_fund(index, &fund);
 			Ok(().into())
 		}
+
+		/// Unreserve the submission deposit of a fund.
+		fn return_deposit(index: FundIndex) -> DispatchResult {
+			let mut fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+
+			T::Currency::unreserve(&fund.beneficiary, fund.deposit);
+			Self::deposit_event(Event::DepositReturned(
+				index,
+				fund.beneficiary.clone(),
+				fund.deposit,
+			));
+
+			fund.deposit = Zero::zero();
+			<Funds<T>>::insert(index, &fund);
+			Ok(())
+		}
+
+		/// Slash the submission deposit of a fund into `T::Slash`.
+		fn slash_deposit(index: FundIndex) -> DispatchResult {
+			let mut fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+
+			let (imbalance, _) = T::Currency::slash_reserved(&fund.beneficiary, fund.deposit);
+			T::Slash::on_unbalanced(imbalance);
+			Self::deposit_event(Event::DepositSlashed(index, fund.deposit));
+
+			fund.deposit = Zero::zero();
+			<Funds<T>>::insert(index, &fund);
+			Ok(())
+		}
+
+		/// Remove a fund whose contributions were paid out. Whatever is left in the fund account,
+		/// at least the existential deposit endowing it, is returned to the beneficiary along with
+		/// the submission deposit.
+		fn close_fund(index: FundIndex, fund: &FundInfoOf<T>) {
+			let account = Self::fund_account_id(index);
+			if let Ok(rest) = T::Currency::withdraw(
+				&account,
+				T::Currency::free_balance(&account),
+				WithdrawReasons::TRANSFER,
+				ExistenceRequirement::AllowDeath,
+			) {
+				T::Currency::resolve_creating(&fund.beneficiary, rest);
+			}
+			T::Currency::unreserve(&fund.beneficiary, fund.deposit);
+
+			Self::crowdfund_kill(index);
+			<Funds<T>>::remove(index);
+		}
+
+		/// Pay `amount` of the funds raised by `fund` out of its fund account. `T::Assets` adds the
+		/// dust of a dying account to the transfer, so an asset fund account is kept alive until
+		/// the last of the raised funds is paid out.
+		fn pay_out(
+			index: FundIndex,
+			fund: &FundInfoOf<T>,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let existence = match fund.asset {
+				PaymentAsset::Asset(_) if amount < fund.raised => ExistenceRequirement::KeepAlive,
+				_ => ExistenceRequirement::AllowDeath,
+			};
+			Self::transfer_fund_asset(fund, &Self::fund_account_id(index), to, amount, existence)
+		}
+
+		/// Transfer `amount` of the asset contributions to `fund` are paid in.
+		fn transfer_fund_asset(
+			fund: &FundInfoOf<T>,
+			from: &T::AccountId,
+			to: &T::AccountId,
+			amount: BalanceOf<T>,
+			existence: ExistenceRequirement,
+		) -> DispatchResult {
+			match fund.asset {
+				PaymentAsset::Native => T::Currency::transfer(from, to, amount, existence),
+				PaymentAsset::Asset(asset_id) => {
+					let keep_alive = existence == ExistenceRequirement::KeepAlive;
+					T::Assets::transfer(asset_id, from, to, amount, keep_alive).map(|_| ())
+				},
+			}
+		}
+
+		/// Ensure `who` can pay `amount` into the fund of `index` in the fund asset.
+		fn ensure_can_pay(
+			index: FundIndex,
+			who: &T::AccountId,
+			amount: BalanceOf<T>,
+		) -> DispatchResult {
+			let fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+			let can_pay = match fund.asset {
+				PaymentAsset::Native => T::Currency::free_balance(who)
+					.checked_sub(&amount)
+					.map_or(false, |new_balance| {
+						T::Currency::ensure_can_withdraw(
+							who,
+							amount,
+							WithdrawReasons::TRANSFER,
+							new_balance,
+						)
+						.is_ok()
+					}),
+				PaymentAsset::Asset(asset_id) =>
+					T::Assets::can_withdraw(asset_id, who, amount).into_result().is_ok(),
+			};
+			ensure!(can_pay, Error::<T>::InsufficientBalance);
+			Ok(())
+		}
+
+		fn ensure_fund_failed(fund: &FundInfoOf<T>) -> DispatchResult {
+			let now = <frame_system::Pallet<T>>::block_number();
+			ensure!(now > fund.end, Error::<T>::FundStillActive);
+			ensure!(fund.raised < fund.goal, Error::<T>::FundSucceeded);
+			Ok(())
+		}
+
+		fn refund_contribution(index: FundIndex, contributor: &T::AccountId) -> DispatchResult {
+			let mut fund = Self::funds(index).ok_or(Error::<T>::InvalidFundIndex)?;
+			let now = <frame_system::Pallet<T>>::block_number();
+			Self::ensure_fund_failed(&fund)?;
+
+			let balance = Self::contribution_get(index, contributor);
+			ensure!(!balance.is_zero(), Error::<T>::NoContribution);
+
+			Self::pay_out(index, &fund, contributor, balance)?;
+
+			Self::contribution_kill(index, contributor);
+			fund.raised = fund.raised.saturating_sub(balance);
+			<Funds<T>>::insert(index, &fund);
+
+			// The items the contributor holds are burned along with the refund, so a failed sale
+			// leaves no items that were paid for with refunded contributions.
+			if let Some(mut collection) = Self::get_collections(&index) {
+				for nft_id in Self::get_collection_owner_nft(index, contributor) {
+					Self::remove_nft(index, &mut collection, nft_id, contributor);
+					Self::deposit_event(Event::Burned(
+						index,
+						nft_id,
+						contributor.clone(),
+						Zero::zero(),
+					));
+				}
+				<Collections<T>>::insert(&index, collection);
+			}
+
+			Self::deposit_event(Event::Withdrew(contributor.clone(), index, balance, now));
+			Ok(())
+		}
+
+		/// Find the ID associated with the fund
+		///
+		/// Each fund stores information about its contributors and their contributions in a child
+		/// trie. This helper function calculates the id of the associated child trie.
+		pub fn id_from_index(index: FundIndex) -> child::ChildInfo {
+			let mut buf = Vec::new();
+			buf.extend_from_slice(b"crowdfnd");
+			buf.extend_from_slice(&index.to_le_bytes()[..]);
+
+			child::ChildInfo::new_default(T::Hashing::hash(&buf[..]).as_ref())
+		}
+
+		/// Record a contribution in the associated child trie.
+		pub fn contribution_put(index: FundIndex, who: &T::AccountId, balance: &BalanceOf<T>) {
+			let id = Self::id_from_index(index);
+			who.using_encoded(|b| child::put(&id, b, &balance));
+		}
+
+		/// Lookup a contribution in the associated child trie.
+		pub fn contribution_get(index: FundIndex, who: &T::AccountId) -> BalanceOf<T> {
+			let id = Self::id_from_index(index);
+			who.using_encoded(|b| child::get_or_default::<BalanceOf<T>>(&id, b))
+		}
+
+		/// Remove a contribution from an associated child trie.
+		pub fn contribution_kill(index: FundIndex, who: &T::AccountId) {
+			let id = Self::id_from_index(index);
+			who.using_encoded(|b| child::kill(&id, b));
+		}
+
+		/// Remove the entire record of contributions in the associated child trie in a single
+		/// storage write.
+		pub fn crowdfund_kill(index: FundIndex) {
+			let id = Self::id_from_index(index);
+			let _ = child::kill_storage(&id, None);
+		}
 	}
 
-	impl<T: Config> frame_support::traits::tokens::nonfungibles::Inspect<T::AccountId> for Pallet<T> {
+	impl<T: Config> nonfungibles::Inspect<T::AccountId> for Pallet<T> {
 		type InstanceId = NFTId;
 		type ClassId = CollectionId;
 		/// Returns the owner of asset `instance` of `class`, or `None` if the asset doesn't exist (or
 		/// somehow has no owner).
-		fn owner(_class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
-			<NFTOwned<T>>::get(&instance)
+		fn owner(class: &Self::ClassId, instance: &Self::InstanceId) -> Option<T::AccountId> {
+			<NFTOwned<T>>::get(&class, &instance)
+		}
+
+		/// Returns the owner of the collection `class`.
+		fn class_owner(class: &Self::ClassId) -> Option<T::AccountId> {
+			Self::get_collections(class).map(|collection| collection.owner)
+		}
+
+		/// Returns the attribute value of `instance` of `class` corresponding to `key`.
+		fn attribute(
+			class: &Self::ClassId,
+			instance: &Self::InstanceId,
+			key: &[u8],
+		) -> Option<Vec<u8>> {
+			let key = BoundedVec::<u8, T::KeyLimit>::try_from(key.to_vec()).ok()?;
+			<Attributes<T>>::get((*class, Some(*instance), key)).map(|(value, _)| value.into())
+		}
+
+		/// Returns the attribute value of `class` corresponding to `key`.
+		fn class_attribute(class: &Self::ClassId, key: &[u8]) -> Option<Vec<u8>> {
+			let key = BoundedVec::<u8, T::KeyLimit>::try_from(key.to_vec()).ok()?;
+			<Attributes<T>>::get((*class, None::<NFTId>, key)).map(|(value, _)| value.into())
+		}
+
+		/// Returns `true` if `instance` of `class` may be transferred: neither the item nor its
+		/// collection is frozen, and the collection is not soulbound.
+		fn can_transfer(class: &Self::ClassId, instance: &Self::InstanceId) -> bool {
+			Self::ensure_transferable(*class, *instance).is_ok()
+		}
+	}
+
+	impl<T: Config> nonfungibles::InspectEnumerable<T::AccountId> for Pallet<T> {
+		/// Returns an iterator of the collections in existence.
+		fn classes() -> Box<dyn Iterator<Item = Self::ClassId>> {
+			Box::new(<Collections<T>>::iter_keys())
+		}
+
+		/// Returns an iterator of the items of a collection in existence.
+		fn instances(class: &Self::ClassId) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+			Box::new(<NFTOwned<T>>::iter_key_prefix(class))
+		}
+
+		/// Returns an iterator of the items of all collections owned by `who`.
+		fn owned(
+			who: &T::AccountId,
+		) -> Box<dyn Iterator<Item = (Self::ClassId, Self::InstanceId)>> {
+			let who = who.clone();
+			Box::new(
+				<CollectionOwnerNFT<T>>::iter()
+					.filter(move |(_, owner, _)| *owner == who)
+					.flat_map(|(collection_id, _, nft_ids)| {
+						nft_ids.into_iter().map(move |nft_id| (collection_id, nft_id))
+					}),
+			)
+		}
+
+		/// Returns an iterator of the items of `class` owned by `who`.
+		fn owned_in_class(
+			class: &Self::ClassId,
+			who: &T::AccountId,
+		) -> Box<dyn Iterator<Item = Self::InstanceId>> {
+			Box::new(<CollectionOwnerNFT<T>>::get(class, who).into_iter())
+		}
+	}
+
+	impl<T: Config> nonfungibles::Create<T::AccountId> for Pallet<T> {
+		/// Create a collection without a fund, so it can never be approved for a sale and its items
+		/// are only minted through `Mutate`. `who` owns it and `admin` holds all of its roles.
+		/// `class` must be the next free collection id.
+		fn create_class(
+			class: &Self::ClassId,
+			who: &T::AccountId,
+			admin: &T::AccountId,
+		) -> DispatchResult {
+			let collection_id = <FundCount<T>>::get();
+			ensure!(*class == collection_id, Error::<T>::InvalidCollectionId);
+			let next_id = collection_id.checked_add(1).ok_or(Error::<T>::StorageOverflow)?;
+
+			let collection = CollectionInfo::<T> {
+				id: collection_id,
+				owner: who.clone(),
+				name: Default::default(),
+				description: Default::default(),
+				base_uri: Default::default(),
+				number_of_items: u32::MAX,
+				number_of_minted: 0,
+				is_frozen: false,
+				project_status: ProjectStatus::Draft,
+				mint_fee: Zero::zero(),
+				start_date: None,
+				end_date: None,
+				sale_window_unit: SaleWindowUnit::BlockNumber,
+				redeemable: false,
+				number_of_burned: 0,
+				royalty: None,
+				sale_mode: SaleMode::FixedPrice,
+				sale_phase: SalePhase::Public,
+				presale: None,
+				max_per_account: None,
+				soulbound: false,
+				issuer: admin.clone(),
+				admin: admin.clone(),
+				freezer: admin.clone(),
+				slug: Default::default(),
+			};
+			<Collections<T>>::insert(collection_id, collection);
+			<FundCount<T>>::put(next_id);
+
+			Self::deposit_event(Event::CollectionRegistered(collection_id));
+			Ok(())
+		}
+	}
+
+	#[derive(Clone, Copy, Encode, Decode, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen)]
+	pub struct DestroyWitness {
+		/// The number of outstanding items of the collection.
+		#[codec(compact)]
+		pub items: u32,
+	}
+
+	#[derive(
+		Clone, Copy, Encode, Decode, Default, PartialEq, Eq, RuntimeDebug, TypeInfo, MaxEncodedLen,
+	)]
+	pub struct CancelWitness {
+		/// The number of outstanding items of the collection.
+		#[codec(compact)]
+		pub items: u32,
+		/// The number of items of the collection with metadata.
+		#[codec(compact)]
+		pub item_metadata: u32,
+		/// The number of attributes of the collection and its items.
+		#[codec(compact)]
+		pub attributes: u32,
+	}
+
+	impl<T: Config> nonfungibles::Destroy<T::AccountId> for Pallet<T> {
+		type DestroyWitness = DestroyWitness;
+
+		fn get_destroy_witness(class: &Self::ClassId) -> Option<Self::DestroyWitness> {
+			Self::get_collections(class)
+				.map(|collection| DestroyWitness { items: collection.number_of_minted })
+		}
+
+		/// Destroy a collection and all of its items. Its fund must be dispensed or dissolved
+		/// first, so no contributions are left behind, and none of its items may be
+		/// fractionalized.
+		fn destroy(
+			class: Self::ClassId,
+			witness: Self::DestroyWitness,
+			maybe_check_owner: Option<T::AccountId>,
+		) -> Result<Self::DestroyWitness, DispatchError> {
+			let collection =
+				Self::get_collections(&class).ok_or(<Error<T>>::CollectionNotExists)?;
+			if let Some(check_owner) = maybe_check_owner {
+				ensure!(collection.owner == check_owner, Error::<T>::NoPermission);
+			}
+			ensure!(collection.number_of_minted == witness.items, Error::<T>::BadWitness);
+			ensure!(!<Funds<T>>::contains_key(class), Error::<T>::FundExists);
+			ensure!(
+				<Fractions<T>>::iter_prefix(class).next().is_none(),
+				Error::<T>::Fractionalized
+			);
+
+			Self::remove_collection(class);
+
+			Self::deposit_event(Event::Destroyed(class));
+			Ok(DestroyWitness { items: collection.number_of_minted })
+		}
+	}
+
+	impl<T: Config> nonfungibles::Mutate<T::AccountId> for Pallet<T> {
+		/// Mint `instance` of `class` to `who` without charging a mint fee. Items are minted in
+		/// order, so `instance` must be the next item id of the collection.
+		fn mint_into(
+			class: &Self::ClassId,
+			instance: &Self::InstanceId,
+			who: &T::AccountId,
+		) -> DispatchResult {
+			let mut collection =
+				Self::get_collections(class).ok_or(<Error<T>>::CollectionNotExists)?;
+			let nft_id = Self::next_nft_id(&collection);
+			ensure!(*instance == nft_id, Error::<T>::InvalidItemIndex);
+			ensure!(nft_id < collection.number_of_items, Error::<T>::SoldOut);
+
+			Self::insert_nft(*class, &mut collection, who)?;
+			Ok(())
+		}
+
+		/// Burn `instance` of `class`, paying its owner the redeemable share of the fund.
+		fn burn_from(class: &Self::ClassId, instance: &Self::InstanceId) -> DispatchResult {
+			let owner = <NFTOwned<T>>::get(class, instance).ok_or(<Error<T>>::NFTNotExists)?;
+			Self::do_burn(*class, *instance, &owner)
+		}
+
+		fn set_attribute(
+			class: &Self::ClassId,
+			instance: &Self::InstanceId,
+			key: &[u8],
+			value: &[u8],
+		) -> DispatchResult {
+			Self::do_set_attribute(*class, Some(*instance), key, value, None)
 		}
 
-		/// Returns the owner of the asset `class`, if there is one. For many NFTs this may not make
-		/// any sense, so users of this API should not be surprised to find an asset class results in
-		/// `None` here.
-		fn class_owner(_class: &Self::ClassId) -> Option<T::AccountId> {
-			None
+		fn set_class_attribute(class: &Self::ClassId, key: &[u8], value: &[u8]) -> DispatchResult {
+			Self::do_set_attribute(*class, None, key, value, None)
+		}
+	}
+
+	impl<T: Config> nonfungibles::Transfer<T::AccountId> for Pallet<T> {
+		/// Transfer `instance` of `class` from its owner to `destination`.
+		fn transfer(
+			class: &Self::ClassId,
+			instance: &Self::InstanceId,
+			destination: &T::AccountId,
+		) -> DispatchResult {
+			let owner = <NFTOwned<T>>::get(class, instance).ok_or(<Error<T>>::NFTNotExists)?;
+			Self::do_transfer(*class, *instance, &owner, destination)
 		}
 	}
 
@@ -0,0 +1,858 @@
+//! Storage migrations for pallet-collection.
+//!
+//! Every migration decodes the layout left behind by the previous version and writes its own frozen
+//! layout, so migrations can be chained from any on-chain version up to the current one.
+
+use super::*;
+use frame_support::{
+	inherent::Vec,
+	pallet_prelude::*,
+	sp_runtime::traits::{Hash, UniqueSaturatedInto, Zero},
+	storage::migration::{get_storage_value, put_storage_value, storage_iter, storage_key_iter},
+	traits::{GetStorageVersion, PalletInfoAccess, StorageVersion},
+	Blake2_128Concat, Twox64Concat,
+};
+
+/// Re-encode every `Collections` value from the `Old` layout to the `New` one. Returns the number of
+/// migrated collections.
+fn translate_collections<T: Config, Old: Decode, New: Encode>(
+	mut f: impl FnMut(CollectionId, Old) -> New,
+) -> Weight {
+	let module = Pallet::<T>::name().as_bytes();
+	let old: Vec<(CollectionId, Old)> =
+		storage_key_iter::<CollectionId, Old, Twox64Concat>(module, b"Collections").collect();
+	let count = old.len() as Weight;
+	for (collection_id, collection) in old {
+		let key = Twox64Concat::hash(&collection_id.encode());
+		put_storage_value(module, b"Collections", &key, f(collection_id, collection));
+	}
+	count
+}
+
+/// Re-encode every `Funds` value from the `Old` layout to the `New` one. Returns the number of
+/// migrated funds.
+fn translate_funds<T: Config, Old: Decode, New: Encode>(
+	mut f: impl FnMut(FundIndex, Old) -> New,
+) -> Weight {
+	let module = Pallet::<T>::name().as_bytes();
+	let old: Vec<(FundIndex, Old)> =
+		storage_key_iter::<FundIndex, Old, Blake2_128Concat>(module, b"Funds").collect();
+	let count = old.len() as Weight;
+	for (index, fund) in old {
+		let key = Blake2_128Concat::hash(&index.encode());
+		put_storage_value(module, b"Funds", &key, f(index, fund));
+	}
+	count
+}
+
+/// Re-encode every value of the `NFTs`-like double map `item`, keeping its raw keys. Returns the
+/// number of migrated items.
+fn translate_items<T: Config, Old: Decode, New: Encode>(
+	item: &[u8],
+	mut f: impl FnMut(Old) -> New,
+) -> Weight {
+	let module = Pallet::<T>::name().as_bytes();
+	let old: Vec<(Vec<u8>, Old)> = storage_iter::<Old>(module, item).collect();
+	let count = old.len() as Weight;
+	for (key, value) in old {
+		put_storage_value(module, item, &key, f(value));
+	}
+	count
+}
+
+/// Truncate `value` to the length bound `S`.
+fn truncate<S: Get<u32>>(mut value: Vec<u8>) -> Vec<u8> {
+	value.truncate(S::get() as usize);
+	value
+}
+
+/// Layouts before any migration ran.
+pub mod v0 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct NFT {
+		pub id: u16,
+		pub name: Vec<u8>,
+		pub image_url: Vec<u8>,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub id: CollectionId,
+		pub owner: AccountId,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		pub number_of_items: u16,
+		pub number_of_minted: u16,
+		pub is_frozen: bool,
+		pub project_status: ProjectStatus,
+		pub mint_fee: Balance,
+		pub start_date: Option<u32>,
+		pub end_date: Option<u32>,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct FundInfo<AccountId, Balance, BlockNumber> {
+		pub beneficiary: AccountId,
+		pub deposit: Balance,
+		pub raised: Balance,
+		pub end: BlockNumber,
+	}
+}
+
+/// Migrate from the global `NFTId` namespace to collection-scoped NFT identity.
+///
+/// `NFTOwned`, `NFTMap` and `NFTApprovals` were keyed only by a `u16` item id, so items from
+/// different collections overwrote each other. They are re-keyed by `(CollectionId, NFTId)` and
+/// `NFTId` is widened to `u32`. Ownership is rebuilt from `CollectionOwnerNFT`, which was already
+/// scoped by collection; pending approvals can not be attributed to a collection and are dropped.
+pub mod v1 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct NFT {
+		pub id: u32,
+		pub name: Vec<u8>,
+		pub image_url: Vec<u8>,
+	}
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub id: CollectionId,
+		pub owner: AccountId,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		pub number_of_items: u32,
+		pub number_of_minted: u32,
+		pub is_frozen: bool,
+		pub project_status: ProjectStatus,
+		pub mint_fee: Balance,
+		pub start_date: Option<u32>,
+		pub end_date: Option<u32>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 1 {
+			log::info!("pallet-collection: v1 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let module = Pallet::<T>::name().as_bytes();
+		let mut reads: Weight = 1;
+		let mut writes: Weight = 0;
+
+		let migrated = translate_collections::<
+			T,
+			v0::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|_, old| CollectionInfo {
+			id: old.id,
+			owner: old.owner,
+			name: old.name,
+			description: old.description,
+			number_of_items: old.number_of_items.into(),
+			number_of_minted: old.number_of_minted.into(),
+			is_frozen: old.is_frozen,
+			project_status: old.project_status,
+			mint_fee: old.mint_fee,
+			start_date: old.start_date,
+			end_date: old.end_date,
+		});
+		reads += migrated;
+		writes += migrated;
+
+		CollectionOwnerNFT::<T>::translate::<Vec<u16>, _>(|_, _, old| {
+			reads += 1;
+			writes += 1;
+			let ids: Vec<NFTId> = old.into_iter().map(Into::into).collect();
+			// The bound is unchanged, so the widened ids always fit.
+			ids.try_into().ok()
+		});
+
+		// The item key of `NFTs` is widened, so the map can not be translated in place. Its raw key
+		// suffix is `twox64(collection_id) ++ collection_id ++ twox64(nft_id) ++ nft_id`.
+		let old_nfts: Vec<(CollectionId, v0::NFT)> = storage_iter::<v0::NFT>(module, b"NFTs")
+			.drain()
+			.filter_map(|(key, nft)| {
+				reads += 1;
+				writes += 1;
+				CollectionId::decode(&mut key.get(8..)?)
+					.ok()
+					.map(|collection_id| (collection_id, nft))
+			})
+			.collect();
+		writes += storage_iter::<v0::NFT>(module, b"NFTMap").drain().count() as Weight;
+		for (collection_id, old) in old_nfts {
+			let nft = NFT { id: old.id.into(), name: old.name, image_url: old.image_url };
+			let mut key = Twox64Concat::hash(&collection_id.encode());
+			key.extend(Twox64Concat::hash(&nft.id.encode()));
+			put_storage_value(module, b"NFTs", &key, &nft);
+			put_storage_value(module, b"NFTMap", &key, &nft);
+			writes += 2;
+		}
+
+		// Ownership keyed by item id alone is unreliable; drop it and rebuild from the
+		// per-collection owner index.
+		writes += storage_iter::<T::AccountId>(module, b"NFTOwned").drain().count() as Weight;
+		writes += storage_iter::<T::AccountId>(module, b"NFTApprovals").drain().count() as Weight;
+		for (collection_id, who, nft_ids) in CollectionOwnerNFT::<T>::iter() {
+			reads += 1;
+			for nft_id in nft_ids {
+				NFTOwned::<T>::insert(collection_id, nft_id, &who);
+				writes += 1;
+			}
+		}
+
+		StorageVersion::new(1).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v1");
+
+		T::DbWeight::get().reads_writes(reads, writes + 1)
+	}
+}
+
+/// Add a per-collection sale window unit and widen `start_date`/`end_date` to `u64`.
+///
+/// Windows approved before the upgrade are interpreted as block numbers; those that have not ended
+/// yet are scheduled in `SaleWindows` so they emit open/close events.
+pub mod v2 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub id: CollectionId,
+		pub owner: AccountId,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		pub number_of_items: u32,
+		pub number_of_minted: u32,
+		pub is_frozen: bool,
+		pub project_status: ProjectStatus,
+		pub mint_fee: Balance,
+		pub start_date: Option<u64>,
+		pub end_date: Option<u64>,
+		pub sale_window_unit: SaleWindowUnit,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 2 {
+			log::info!("pallet-collection: v2 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let now: u64 = <frame_system::Pallet<T>>::block_number().unique_saturated_into();
+		let mut windows = Vec::new();
+		let migrated = translate_collections::<
+			T,
+			v1::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|collection_id, old| {
+			let start_date = old.start_date.map(u64::from);
+			let end_date = old.end_date.map(u64::from);
+			if old.project_status == ProjectStatus::Approved
+				&& end_date.map_or(true, |end_date| now < end_date)
+			{
+				let opened = start_date.map_or(true, |start_date| now >= start_date);
+				windows.push((collection_id, opened));
+			}
+			CollectionInfo {
+				id: old.id,
+				owner: old.owner,
+				name: old.name,
+				description: old.description,
+				number_of_items: old.number_of_items,
+				number_of_minted: old.number_of_minted,
+				is_frozen: old.is_frozen,
+				project_status: old.project_status,
+				mint_fee: old.mint_fee,
+				start_date,
+				end_date,
+				sale_window_unit: SaleWindowUnit::BlockNumber,
+			}
+		});
+
+		let scheduled = windows.len();
+		windows.truncate(T::MaxActiveSaleWindows::get() as usize);
+		if windows.len() < scheduled {
+			log::warn!(
+				"pallet-collection: {} sale windows exceed MaxActiveSaleWindows and emit no events",
+				scheduled - windows.len()
+			);
+		}
+		if let Ok(windows) = BoundedVec::<_, T::MaxActiveSaleWindows>::try_from(windows) {
+			SaleWindows::<T>::put(windows);
+		}
+
+		StorageVersion::new(2).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v2");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 2)
+	}
+}
+
+/// Add a funding goal to `FundInfo`.
+///
+/// Funds created before the upgrade had no goal and their `end` was the block they were created
+/// in. They get a zero goal, so they always succeed, and end with the sale window of their
+/// collection, so existing collections can keep minting until then. Funds of collections without
+/// a sale window end at the upgrade and can be dispensed right away.
+pub mod v3 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct FundInfo<AccountId, Balance, BlockNumber> {
+		pub beneficiary: AccountId,
+		pub deposit: Balance,
+		pub raised: Balance,
+		pub end: BlockNumber,
+		pub goal: Balance,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 3 {
+			log::info!("pallet-collection: v3 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let module = Pallet::<T>::name().as_bytes();
+		let now = <frame_system::Pallet<T>>::block_number();
+		let migrated = translate_funds::<
+			T,
+			v0::FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+			FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+		>(|index, old| {
+			// Sale windows were migrated to block numbers by v2.
+			let end = get_storage_value::<v2::CollectionInfo<T::AccountId, BalanceOf<T>>>(
+				module,
+				b"Collections",
+				&Twox64Concat::hash(&index.encode()),
+			)
+			.and_then(|collection| collection.end_date)
+			.map_or(now, |end_date| end_date.unique_saturated_into());
+			FundInfo {
+				beneficiary: old.beneficiary,
+				deposit: old.deposit,
+				raised: old.raised,
+				end,
+				goal: Zero::zero(),
+			}
+		});
+
+		StorageVersion::new(3).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v3");
+
+		T::DbWeight::get().reads_writes(migrated * 2 + 1, migrated + 1)
+	}
+}
+
+/// Add a metadata base URI to `CollectionInfo`. Existing collections get an empty base URI; their
+/// minted items keep the image URLs they were minted with.
+pub mod v4 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub id: CollectionId,
+		pub owner: AccountId,
+		pub name: Vec<u8>,
+		pub description: Vec<u8>,
+		pub base_uri: Vec<u8>,
+		pub number_of_items: u32,
+		pub number_of_minted: u32,
+		pub is_frozen: bool,
+		pub project_status: ProjectStatus,
+		pub mint_fee: Balance,
+		pub start_date: Option<u64>,
+		pub end_date: Option<u64>,
+		pub sale_window_unit: SaleWindowUnit,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 4 {
+			log::info!("pallet-collection: v4 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v2::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|_, old| CollectionInfo {
+			id: old.id,
+			owner: old.owner,
+			name: old.name,
+			description: old.description,
+			base_uri: Vec::new(),
+			number_of_items: old.number_of_items,
+			number_of_minted: old.number_of_minted,
+			is_frozen: old.is_frozen,
+			project_status: old.project_status,
+			mint_fee: old.mint_fee,
+			start_date: old.start_date,
+			end_date: old.end_date,
+			sale_window_unit: old.sale_window_unit,
+		});
+
+		StorageVersion::new(4).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v4");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Bound `CollectionInfo.name`/`description` and `NFT.name`/`image_url` by `NameLimit`,
+/// `DescriptionLimit` and `UriLimit`.
+///
+/// The encoding of a `BoundedVec` is the same as that of a `Vec`, so only values exceeding the new
+/// limits change: they are truncated so they still decode.
+pub mod v5 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 5 {
+			log::info!("pallet-collection: v5 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let collections = translate_collections::<
+			T,
+			v4::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			v4::CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|_, mut collection| {
+			collection.name = truncate::<T::NameLimit>(collection.name);
+			collection.description = truncate::<T::DescriptionLimit>(collection.description);
+			collection.base_uri = truncate::<T::UriLimit>(collection.base_uri);
+			collection
+		});
+
+		let bound_nft = |mut nft: v1::NFT| {
+			nft.name = truncate::<T::NameLimit>(nft.name);
+			nft.image_url = truncate::<T::UriLimit>(nft.image_url);
+			nft
+		};
+		let nfts = translate_items::<T, v1::NFT, v1::NFT>(b"NFTs", bound_nft)
+			+ translate_items::<T, v1::NFT, v1::NFT>(b"NFTMap", bound_nft);
+
+		StorageVersion::new(5).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v5");
+
+		T::DbWeight::get().reads_writes(collections + nfts + 1, collections + nfts + 1)
+	}
+}
+
+/// Add `redeemable` and `number_of_burned` to `CollectionInfo`. Existing collections are not
+/// redeemable and have no burned items.
+///
+/// New fields are appended to the end of `CollectionInfo`, so the layout is the previous one
+/// followed by the new fields.
+pub mod v6 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub base: v4::CollectionInfo<AccountId, Balance>,
+		pub redeemable: bool,
+		pub number_of_burned: u32,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 6 {
+			log::info!("pallet-collection: v6 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated =
+			translate_collections::<
+				T,
+				v4::CollectionInfo<T::AccountId, BalanceOf<T>>,
+				CollectionInfo<T::AccountId, BalanceOf<T>>,
+			>(|_, base| CollectionInfo { base, redeemable: false, number_of_burned: 0 });
+
+		StorageVersion::new(6).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v6");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add a royalty to `CollectionInfo`. Existing collections charge no royalty.
+pub mod v7 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub base: v6::CollectionInfo<AccountId, Balance>,
+		pub royalty: Option<RoyaltyInfo<AccountId>>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 7 {
+			log::info!("pallet-collection: v7 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v6::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|_, base| CollectionInfo { base, royalty: None });
+
+		StorageVersion::new(7).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v7");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add a sale mode to `CollectionInfo`. Existing collections keep selling at a fixed price.
+pub mod v8 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance> {
+		pub base: v7::CollectionInfo<AccountId, Balance>,
+		pub sale_mode: SaleMode<Balance>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 8 {
+			log::info!("pallet-collection: v8 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v7::CollectionInfo<T::AccountId, BalanceOf<T>>,
+			CollectionInfo<T::AccountId, BalanceOf<T>>,
+		>(|_, base| CollectionInfo { base, sale_mode: SaleMode::FixedPrice });
+
+		StorageVersion::new(8).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v8");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add a sale phase and presale to `CollectionInfo`. Existing collections are in their public
+/// phase without presale.
+pub mod v9 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance, Hash> {
+		pub base: v8::CollectionInfo<AccountId, Balance>,
+		pub sale_phase: SalePhase,
+		pub presale: Option<PresaleInfo<Hash, Balance>>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 9 {
+			log::info!("pallet-collection: v9 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated =
+			translate_collections::<
+				T,
+				v8::CollectionInfo<T::AccountId, BalanceOf<T>>,
+				CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+			>(|_, base| CollectionInfo { base, sale_phase: SalePhase::Public, presale: None });
+
+		StorageVersion::new(9).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v9");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add `max_per_account` to `CollectionInfo`. Existing collections have no per-account limit.
+pub mod v10 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance, Hash> {
+		pub base: v9::CollectionInfo<AccountId, Balance, Hash>,
+		pub max_per_account: Option<u32>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 10 {
+			log::info!("pallet-collection: v10 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v9::CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+			CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+		>(|_, base| CollectionInfo { base, max_per_account: None });
+
+		StorageVersion::new(10).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v10");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add the payment asset to `FundInfo`. Existing funds are paid in `T::Currency`.
+pub mod v11 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct FundInfo<AccountId, Balance, BlockNumber, AssetId> {
+		pub base: v3::FundInfo<AccountId, Balance, BlockNumber>,
+		pub asset: PaymentAsset<AssetId>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 11 {
+			log::info!("pallet-collection: v11 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_funds::<
+			T,
+			v3::FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber>,
+			FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber, T::AssetId>,
+		>(|_, base| FundInfo { base, asset: PaymentAsset::Native });
+
+		StorageVersion::new(11).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v11");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add the soulbound flag to `CollectionInfo`. Existing collections stay transferable.
+pub mod v12 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance, Hash> {
+		pub base: v10::CollectionInfo<AccountId, Balance, Hash>,
+		pub soulbound: bool,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 12 {
+			log::info!("pallet-collection: v12 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v10::CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+			CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+		>(|_, base| CollectionInfo { base, soulbound: false });
+
+		StorageVersion::new(12).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v12");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add the issuer, admin and freezer roles to `CollectionInfo`. Every role starts with the collection
+/// owner.
+pub mod v13 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance, Hash> {
+		pub base: v12::CollectionInfo<AccountId, Balance, Hash>,
+		pub issuer: AccountId,
+		pub admin: AccountId,
+		pub freezer: AccountId,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 13 {
+			log::info!("pallet-collection: v13 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_collections::<
+			T,
+			v12::CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+			CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+		>(|_, base| {
+			let owner = base.base.base.base.base.base.owner.clone();
+			CollectionInfo { base, issuer: owner.clone(), admin: owner.clone(), freezer: owner }
+		});
+
+		StorageVersion::new(13).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v13");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Reserve the `SubmissionDeposit` from the fund beneficiary instead of keeping it in the fund
+/// account. Deposits still held by a fund account are moved back to the beneficiary and reserved.
+pub mod v14 {
+	use super::*;
+	use frame_support::traits::{Currency, ExistenceRequirement, ReservableCurrency};
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 14 {
+			log::info!("pallet-collection: v14 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let module = Pallet::<T>::name().as_bytes();
+		let funds: Vec<(
+			FundIndex,
+			v11::FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber, T::AssetId>,
+		)> = storage_key_iter::<FundIndex, _, Blake2_128Concat>(module, b"Funds").collect();
+		let count = funds.len() as Weight;
+		let mut moved: Weight = 0;
+		for (index, fund) in funds {
+			let fund = fund.base;
+			if fund.deposit.is_zero() {
+				continue
+			}
+			let moved_back = T::Currency::transfer(
+				&Pallet::<T>::fund_account_id(index),
+				&fund.beneficiary,
+				fund.deposit,
+				ExistenceRequirement::AllowDeath,
+			)
+			.and_then(|()| T::Currency::reserve(&fund.beneficiary, fund.deposit));
+			if let Err(e) = moved_back {
+				log::warn!(
+					"pallet-collection: could not reserve the deposit of fund {}: {:?}",
+					index,
+					e
+				);
+			}
+			moved += 1;
+		}
+
+		StorageVersion::new(14).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v14");
+
+		T::DbWeight::get().reads_writes(count + moved * 4 + 1, moved * 4 + 1)
+	}
+}
+
+/// Add the protocol fees taken from contributions to `FundInfo`. Existing funds paid none.
+pub mod v15 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct FundInfo<AccountId, Balance, BlockNumber, AssetId> {
+		pub base: v11::FundInfo<AccountId, Balance, BlockNumber, AssetId>,
+		pub fees: Balance,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 15 {
+			log::info!("pallet-collection: v15 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let migrated = translate_funds::<
+			T,
+			v11::FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber, T::AssetId>,
+			FundInfo<T::AccountId, BalanceOf<T>, T::BlockNumber, T::AssetId>,
+		>(|_, base| FundInfo { base, fees: Zero::zero() });
+
+		StorageVersion::new(15).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v15");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
+
+/// Add the slug of the collection name to `CollectionInfo` and index it in `CollectionByName`.
+/// Collections whose slug is already taken by an older collection keep their slug but are not
+/// indexed. Rejected collections get no slug, leaving their name free.
+pub mod v16 {
+	use super::*;
+
+	#[derive(Encode, Decode)]
+	pub struct CollectionInfo<AccountId, Balance, Hash> {
+		pub base: v13::CollectionInfo<AccountId, Balance, Hash>,
+		pub slug: Vec<u8>,
+	}
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 16 {
+			log::info!("pallet-collection: v16 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut indexed: Weight = 0;
+		let migrated = translate_collections::<
+			T,
+			v13::CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+			CollectionInfo<T::AccountId, BalanceOf<T>, T::Hash>,
+		>(|collection_id, base| {
+			let info = &base.base.base.base.base.base.base;
+			let slug = if info.project_status == ProjectStatus::Rejected {
+				Vec::new()
+			} else {
+				Pallet::<T>::slugify(&info.name)
+			};
+			if !slug.is_empty() {
+				let slug_hash = T::Hashing::hash_of(&slug);
+				if CollectionByName::<T>::contains_key(slug_hash) {
+					log::warn!(
+						"pallet-collection: the slug of collection {} is already taken",
+						collection_id
+					);
+				} else {
+					CollectionByName::<T>::insert(slug_hash, collection_id);
+					indexed += 1;
+				}
+			}
+			CollectionInfo { base, slug }
+		});
+
+		StorageVersion::new(16).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v16");
+
+		T::DbWeight::get().reads_writes(migrated * 2 + 1, migrated + indexed + 1)
+	}
+}
+
+/// Add the deposit reserved for an item metadata URI to `ItemMetadata`. URIs uploaded before the
+/// upgrade hold no deposit.
+pub mod v17 {
+	use super::*;
+
+	pub fn migrate<T: Config>() -> Weight {
+		let on_chain_version = Pallet::<T>::on_chain_storage_version();
+		if on_chain_version >= 17 {
+			log::info!("pallet-collection: v17 migration already applied, skipping");
+			return T::DbWeight::get().reads(1);
+		}
+
+		let mut migrated: Weight = 0;
+		ItemMetadata::<T>::translate::<BoundedVec<u8, T::UriLimit>, _>(|_, _, uri| {
+			migrated += 1;
+			Some((uri, None))
+		});
+
+		StorageVersion::new(17).put::<Pallet<T>>();
+		log::info!("pallet-collection: migrated to v17");
+
+		T::DbWeight::get().reads_writes(migrated + 1, migrated + 1)
+	}
+}
@@ -8,7 +8,11 @@ use sp_runtime::{
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Test>;
 type Block = frame_system::mocking::MockBlock<Test>;
-use frame_support::traits::{ConstU16, ConstU64};
+use frame_support::traits::{
+	tokens::fungibles, ConstU16, ConstU32, ConstU64, Currency, Get, OnUnbalanced,
+};
+use sp_runtime::Perbill;
+use std::cell::RefCell;
 
 // Configure a mock runtime to test the pallet.
 frame_support::construct_runtime!(
@@ -18,6 +22,10 @@ frame_support::construct_runtime!(
 		UncheckedExtrinsic = UncheckedExtrinsic,
 	{
 		System: frame_system::{Pallet, Call, Config, Storage, Event<T>},
+		Balances: pallet_balances::{Pallet, Call, Storage, Config<T>, Event<T>},
+		RandomnessCollectiveFlip: pallet_randomness_collective_flip::{Pallet, Storage},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
+		Assets: pallet_assets::{Pallet, Call, Storage, Config<T>, Event<T>},
 		AprilDaoModule: pallet_collection::{Pallet, Call, Storage, Event<T>},
 	}
 );
@@ -33,14 +41,14 @@ impl system::Config for Test {
 	type BlockNumber = u64;
 	type Hash = H256;
 	type Hashing = BlakeTwo256;
-	type AccountId = u64;
+	type AccountId = u128;
 	type Lookup = IdentityLookup<Self::AccountId>;
 	type Header = Header;
 	type Event = Event;
 	type BlockHashCount = ConstU64<250>;
 	type Version = ();
 	type PalletInfo = PalletInfo;
-	type AccountData = ();
+	type AccountData = pallet_balances::AccountData<u64>;
 	type OnNewAccount = ();
 	type OnKilledAccount = ();
 	type SystemWeightInfo = ();
@@ -49,11 +57,136 @@ impl system::Config for Test {
 	type MaxConsumers = frame_support::traits::ConstU32<16>;
 }
 
+impl pallet_balances::Config for Test {
+	type MaxLocks = ();
+	type MaxReserves = ();
+	type ReserveIdentifier = [u8; 8];
+	type Balance = u64;
+	type Event = Event;
+	type DustRemoval = ();
+	type ExistentialDeposit = ExistentialDeposit;
+	type AccountStore = System;
+	type WeightInfo = ();
+}
+
+impl pallet_randomness_collective_flip::Config for Test {}
+
+impl pallet_timestamp::Config for Test {
+	type Moment = u64;
+	type OnTimestampSet = ();
+	type MinimumPeriod = ConstU64<5>;
+	type WeightInfo = ();
+}
+
+impl pallet_assets::Config for Test {
+	type Event = Event;
+	type Balance = u64;
+	type AssetId = u32;
+	type Currency = Balances;
+	type ForceOrigin = frame_system::EnsureRoot<u128>;
+	type AssetDeposit = ConstU64<1>;
+	type AssetAccountDeposit = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<1>;
+	type MetadataDepositPerByte = ConstU64<1>;
+	type ApprovalDeposit = ConstU64<1>;
+	type StringLimit = ConstU32<50>;
+	type Freezer = ();
+	type Extra = ();
+	type WeightInfo = ();
+}
+
+thread_local! {
+	static EXISTENTIAL_DEPOSIT: RefCell<u64> = RefCell::new(1);
+	static PROTOCOL_FEE: RefCell<Perbill> = RefCell::new(Perbill::zero());
+}
+
+pub struct ExistentialDeposit;
+impl Get<u64> for ExistentialDeposit {
+	fn get() -> u64 {
+		EXISTENTIAL_DEPOSIT.with(|ed| *ed.borrow())
+	}
+}
+
+/// Set the existential deposit of the native currency. Tests run with an existential deposit
+/// of 1 by default.
+pub fn set_existential_deposit(ed: u64) {
+	EXISTENTIAL_DEPOSIT.with(|v| *v.borrow_mut() = ed);
+}
+
+pub struct ProtocolFee;
+impl Get<Perbill> for ProtocolFee {
+	fn get() -> Perbill {
+		PROTOCOL_FEE.with(|fee| *fee.borrow())
+	}
+}
+
+/// Set the protocol fee taken from contributions. Tests run without fee by default.
+pub fn set_protocol_fee(fee: Perbill) {
+	PROTOCOL_FEE.with(|v| *v.borrow_mut() = fee);
+}
+
+/// Credit native protocol fees to the fee destination account 99.
+pub struct FeeToDestination;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Test>> for FeeToDestination {
+	fn on_nonzero_unbalanced(fee: pallet_balances::NegativeImbalance<Test>) {
+		Balances::resolve_creating(&99, fee);
+	}
+}
+
+/// Credit asset protocol fees to the fee destination account 99.
+pub struct AssetFeeToDestination;
+impl OnUnbalanced<fungibles::CreditOf<u128, Assets>> for AssetFeeToDestination {
+	fn on_nonzero_unbalanced(fee: fungibles::CreditOf<u128, Assets>) {
+		let _ = <Assets as fungibles::Balanced<u128>>::resolve(&99, fee);
+	}
+}
+
 impl pallet_collection::Config for Test {
 	type Event = Event;
+	type Currency = Balances;
+	type CollectionRandomness = RandomnessCollectiveFlip;
+	type SubmissionDeposit = ConstU64<10>;
+	type ApproveOrigin = frame_system::EnsureRoot<u128>;
+	type Slash = ();
+	type MaxNFTOwned = ConstU32<3>;
+	type Timestamp = Timestamp;
+	type MaxActiveSaleWindows = ConstU32<2>;
+	type UriLimit = ConstU32<32>;
+	type NameLimit = ConstU32<16>;
+	type DescriptionLimit = ConstU32<32>;
+	type AssetId = u32;
+	type Assets = Assets;
+	type MaxMintPerTx = ConstU32<3>;
+	type KeyLimit = ConstU32<16>;
+	type ValueLimit = ConstU32<32>;
+	type MaxMilestones = ConstU32<3>;
+	type ProtocolFee = ProtocolFee;
+	type FeeHandler = FeeToDestination;
+	type AssetFeeHandler = AssetFeeToDestination;
+	type AttributeDepositBase = ConstU64<5>;
+	type DepositPerByte = ConstU64<1>;
+	type MetadataDepositBase = ConstU64<2>;
+	type WeightInfo = ();
 }
 
 // Build genesis storage according to the mock runtime.
 pub fn new_test_ext() -> sp_io::TestExternalities {
-	system::GenesisConfig::default().build_storage::<Test>().unwrap().into()
+	set_existential_deposit(1);
+	set_protocol_fee(Perbill::zero());
+	let mut t = system::GenesisConfig::default().build_storage::<Test>().unwrap();
+	pallet_balances::GenesisConfig::<Test> {
+		balances: vec![(1, 1_000), (2, 1_000), (3, 1_000), (4, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	pallet_assets::GenesisConfig::<Test> {
+		assets: vec![(0, 1, true, 1)],
+		metadata: vec![],
+		accounts: vec![(0, 2, 1_000), (0, 3, 1_000)],
+	}
+	.assimilate_storage(&mut t)
+	.unwrap();
+	let mut ext = sp_io::TestExternalities::new(t);
+	ext.execute_with(|| System::set_block_number(1));
+	ext
 }
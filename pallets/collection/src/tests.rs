@@ -1,20 +1,2557 @@
-use crate::{mock::*, Error};
-use frame_support::{assert_noop, assert_ok};
+use crate::{
+	migrations, mock::*, CancelWitness, CollectionConfig, CollectionId, DestroyWitness, Error,
+	Event as CollectionEvent, Milestone, PaymentAsset, ProjectStatus, RoyaltyInfo, SaleMode,
+	SalePhase, SaleWindowUnit,
+};
+use frame_support::{
+	assert_noop, assert_ok,
+	codec::Encode,
+	dispatch::DispatchResult,
+	storage::migration::put_storage_value,
+	traits::{
+		tokens::nonfungibles, Currency, GetStorageVersion, OnRuntimeUpgrade, PalletInfoAccess,
+		StorageVersion,
+	},
+	Blake2_128Concat, StorageHasher, Twox64Concat,
+};
+use sp_core::H256;
+use sp_runtime::{
+	traits::{BadOrigin, BlakeTwo256, Hash},
+	Perbill,
+};
+
+/// A name no registered collection uses yet.
+fn collection_name() -> Vec<u8> {
+	format!("Collection {}", AprilDaoModule::fund_count()).into_bytes()
+}
+
+/// The arguments of `register_collection`. The defaults register a collection of five items at
+/// 10 each, without goal and with a fund ending at block 100, named by `collection_name`.
+struct CollectionParams {
+	owner: u128,
+	name: Option<Vec<u8>>,
+	description: Vec<u8>,
+	base_uri: Vec<u8>,
+	number_of_items: u32,
+	mint_fee: u64,
+	goal: u64,
+	fund_end: u64,
+	redeemable: bool,
+	sale_mode: SaleMode<u64>,
+	max_per_account: Option<u32>,
+	payment_asset: PaymentAsset<u32>,
+	soulbound: bool,
+	milestones: Vec<Milestone<u64, H256, u64>>,
+}
+
+impl Default for CollectionParams {
+	fn default() -> Self {
+		CollectionParams {
+			owner: 1,
+			name: None,
+			description: b"Description".to_vec(),
+			base_uri: b"ipfs://base/".to_vec(),
+			number_of_items: 5,
+			mint_fee: 10,
+			goal: 0,
+			fund_end: 100,
+			redeemable: false,
+			sale_mode: SaleMode::FixedPrice,
+			max_per_account: None,
+			payment_asset: PaymentAsset::Native,
+			soulbound: false,
+			milestones: vec![],
+		}
+	}
+}
+
+impl CollectionParams {
+	fn owner(mut self, owner: u128) -> Self {
+		self.owner = owner;
+		self
+	}
+
+	fn name(mut self, name: &[u8]) -> Self {
+		self.name = Some(name.to_vec());
+		self
+	}
+
+	fn description(mut self, description: &[u8]) -> Self {
+		self.description = description.to_vec();
+		self
+	}
+
+	fn base_uri(mut self, base_uri: &[u8]) -> Self {
+		self.base_uri = base_uri.to_vec();
+		self
+	}
+
+	fn number_of_items(mut self, number_of_items: u32) -> Self {
+		self.number_of_items = number_of_items;
+		self
+	}
+
+	fn goal(mut self, goal: u64) -> Self {
+		self.goal = goal;
+		self
+	}
+
+	fn fund_end(mut self, fund_end: u64) -> Self {
+		self.fund_end = fund_end;
+		self
+	}
+
+	fn redeemable(mut self) -> Self {
+		self.redeemable = true;
+		self
+	}
+
+	fn sale_mode(mut self, sale_mode: SaleMode<u64>) -> Self {
+		self.sale_mode = sale_mode;
+		self
+	}
+
+	fn max_per_account(mut self, max_per_account: u32) -> Self {
+		self.max_per_account = Some(max_per_account);
+		self
+	}
+
+	fn payment_asset(mut self, payment_asset: PaymentAsset<u32>) -> Self {
+		self.payment_asset = payment_asset;
+		self
+	}
+
+	fn soulbound(mut self) -> Self {
+		self.soulbound = true;
+		self
+	}
+
+	fn milestones(mut self, milestones: Vec<Milestone<u64, H256, u64>>) -> Self {
+		self.milestones = milestones;
+		self
+	}
+
+	fn register(self) -> DispatchResult {
+		AprilDaoModule::register_collection(
+			Origin::signed(self.owner),
+			CollectionConfig {
+				name: self.name.unwrap_or_else(collection_name),
+				description: self.description,
+				base_uri: self.base_uri,
+				number_of_items: self.number_of_items,
+				mint_fee: self.mint_fee,
+				goal: self.goal,
+				fund_end: self.fund_end,
+				redeemable: self.redeemable,
+				royalty: None,
+				sale_mode: self.sale_mode,
+				max_per_account: self.max_per_account,
+				payment_asset: self.payment_asset,
+				soulbound: self.soulbound,
+				milestones: self.milestones,
+			},
+		)
+	}
+
+	/// Register the collection and return its id.
+	fn create(self) -> CollectionId {
+		assert_ok!(self.register());
+		AprilDaoModule::fund_count() - 1
+	}
+
+	/// Register the collection and approve a sale window from block `start` to block `end`.
+	fn create_approved(self, start: u64, end: u64) -> CollectionId {
+		let collection_id = self.create();
+		assert_ok!(AprilDaoModule::approve_collection(
+			Origin::root(),
+			collection_id,
+			start,
+			end,
+			SaleWindowUnit::BlockNumber
+		));
+		collection_id
+	}
+}
+
+fn setup_minted_collection() -> CollectionId {
+	let collection_id = CollectionParams::default().create_approved(0, 100);
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+	collection_id
+}
+
+#[test]
+fn transfer_moves_nft_between_owners() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3));
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(3));
+		assert!(AprilDaoModule::get_collection_owner_nft(collection_id, 2).is_empty());
+		assert_eq!(
+			AprilDaoModule::get_collection_owner_nft(collection_id, 3).into_inner(),
+			vec![0]
+		);
+	});
+}
+
+#[test]
+fn transfer_requires_ownership() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(3), collection_id, 0, 4),
+			Error::<Test>::NotNFTOwner
+		);
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 2),
+			Error::<Test>::TransferToSelf
+		);
+	});
+}
+
+#[test]
+fn transfer_from_requires_approval() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_noop!(
+			AprilDaoModule::transfer_from(Origin::signed(3), collection_id, 0, 2, 4),
+			Error::<Test>::NotApproved
+		);
+
+		assert_ok!(AprilDaoModule::approve(Origin::signed(2), collection_id, 0, 3));
+		assert_ok!(AprilDaoModule::transfer_from(Origin::signed(3), collection_id, 0, 2, 4));
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(4));
+		// The approval is consumed by the transfer
+		assert_eq!(AprilDaoModule::approvals(collection_id, 0), None);
+	});
+}
+
+#[test]
+fn cancel_approval_works() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_ok!(AprilDaoModule::approve(Origin::signed(2), collection_id, 0, 3));
+		assert_ok!(AprilDaoModule::cancel_approval(Origin::signed(2), collection_id, 0));
+
+		assert_noop!(
+			AprilDaoModule::transfer_from(Origin::signed(3), collection_id, 0, 2, 4),
+			Error::<Test>::NotApproved
+		);
+		assert_noop!(
+			AprilDaoModule::cancel_approval(Origin::signed(2), collection_id, 0),
+			Error::<Test>::NoApproval
+		);
+	});
+}
+
+#[test]
+fn nft_ownership_is_scoped_by_collection() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::tokens::nonfungibles::Inspect;
+
+		let first = setup_minted_collection();
+		let second = CollectionParams::default().create_approved(0, 100);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), second, 1));
+
+		// Item #0 of each collection keeps its own owner
+		assert_eq!(<AprilDaoModule as Inspect<u128>>::owner(&first, &0), Some(2));
+		assert_eq!(<AprilDaoModule as Inspect<u128>>::owner(&second, &0), Some(3));
+	});
+}
+
+#[test]
+fn mint_respects_block_number_sale_window() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create_approved(5, 10);
+
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::SaleNotStarted
+		);
+
+		System::set_block_number(5);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+
+		System::set_block_number(10);
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::SaleEnded
+		);
+	});
+}
+
+#[test]
+fn mint_respects_moment_sale_window() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_ok!(AprilDaoModule::approve_collection(
+			Origin::root(),
+			collection_id,
+			1_000,
+			2_000,
+			SaleWindowUnit::Moment
+		));
+
+		Timestamp::set_timestamp(999);
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::SaleNotStarted
+		);
+
+		Timestamp::set_timestamp(1_500);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+
+		Timestamp::set_timestamp(2_000);
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::SaleEnded
+		);
+	});
+}
+
+#[test]
+fn approve_collection_rejects_invalid_sale_window() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_noop!(
+			AprilDaoModule::approve_collection(
+				Origin::root(),
+				collection_id,
+				10,
+				10,
+				SaleWindowUnit::BlockNumber
+			),
+			Error::<Test>::InvalidSaleWindow
+		);
+	});
+}
+
+#[test]
+fn sale_window_emits_open_and_close_events() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Hooks;
+
+		let collection_id = CollectionParams::default().create_approved(3, 6);
+
+		System::set_block_number(3);
+		AprilDaoModule::on_initialize(3);
+		System::assert_last_event(CollectionEvent::SaleWindowOpened(collection_id).into());
+
+		System::set_block_number(6);
+		AprilDaoModule::on_initialize(6);
+		System::assert_last_event(CollectionEvent::SaleWindowClosed(collection_id).into());
+		assert!(AprilDaoModule::sale_windows().is_empty());
+	});
+}
+
+fn setup_failed_fund() -> CollectionId {
+	let collection_id = CollectionParams::default().goal(100).fund_end(10).create_approved(0, 10);
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+	assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+	collection_id
+}
+
+#[test]
+fn register_collection_requires_future_fund_end() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CollectionParams::default().goal(100).fund_end(1).register(),
+			Error::<Test>::FundEndTooEarly
+		);
+	});
+}
+
+#[test]
+fn withdraw_returns_contribution_of_failed_fund() {
+	new_test_ext().execute_with(|| {
+		let index = setup_failed_fund();
+		assert_eq!(AprilDaoModule::contribution_get(index, &2), 10);
+
+		assert_noop!(
+			AprilDaoModule::withdraw(Origin::signed(2), index),
+			Error::<Test>::FundStillActive
+		);
+
+		System::set_block_number(11);
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(2), index));
+
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(AprilDaoModule::contribution_get(index, &2), 0);
+		// The refunded item is burned
+		assert_eq!(AprilDaoModule::owner(index, 0), None);
+		assert!(AprilDaoModule::get_collection_owner_nft(index, &2).is_empty());
+		System::assert_has_event(CollectionEvent::Burned(index, 0, 2, 0).into());
+		let collection = AprilDaoModule::get_collections(index).unwrap();
+		assert_eq!((collection.number_of_minted, collection.number_of_burned), (1, 1));
+		assert_eq!(AprilDaoModule::owner(index, 1), Some(3));
+		assert_noop!(
+			AprilDaoModule::withdraw(Origin::signed(2), index),
+			Error::<Test>::NoContribution
+		);
+	});
+}
+
+#[test]
+fn refund_and_dissolve_reap_failed_fund() {
+	new_test_ext().execute_with(|| {
+		let index = setup_failed_fund();
+		System::set_block_number(11);
+
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(2), index));
+		assert_noop!(
+			AprilDaoModule::dissolve(Origin::signed(1), index),
+			Error::<Test>::FundNotEmpty
+		);
+
+		assert_ok!(AprilDaoModule::refund(Origin::signed(1), index, 3));
+		assert_eq!(Balances::free_balance(3), 1_000);
+
+		assert_ok!(AprilDaoModule::dissolve(Origin::signed(1), index));
+		// The submission deposit and the fund account endowment are returned to the creator
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_eq!(Balances::free_balance(AprilDaoModule::fund_account_id(index)), 0);
+		assert!(AprilDaoModule::funds(index).is_none());
+	});
+}
+
+#[test]
+fn successful_fund_is_not_refundable() {
+	new_test_ext().execute_with(|| {
+		let index = CollectionParams::default().goal(10).fund_end(10).create_approved(0, 10);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), index, 1));
+
+		System::set_block_number(11);
+		assert_noop!(
+			AprilDaoModule::withdraw(Origin::signed(2), index),
+			Error::<Test>::FundSucceeded
+		);
+	});
+}
+
+#[test]
+fn dispense_requires_an_ended_successful_fund() {
+	new_test_ext().execute_with(|| {
+		let failed = CollectionParams::default().goal(20).fund_end(10).create();
+		let succeeded = CollectionParams::default().goal(10).fund_end(10).create();
+		for index in [failed, succeeded] {
+			assert_ok!(AprilDaoModule::approve_collection(
+				Origin::root(),
+				index,
+				0,
+				10,
+				SaleWindowUnit::BlockNumber
+			));
+			assert_ok!(AprilDaoModule::mint(Origin::signed(2), index, 1));
+		}
+
+		assert_noop!(
+			AprilDaoModule::dispense_fund(Origin::signed(1), succeeded, 4),
+			Error::<Test>::FundStillActive
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			AprilDaoModule::dispense_fund(Origin::signed(1), failed, 4),
+			Error::<Test>::GoalNotReached
+		);
+		assert_ok!(AprilDaoModule::dispense_fund(Origin::signed(1), succeeded, 4));
+		assert_eq!(Balances::free_balance(4), 1_010);
+		assert!(AprilDaoModule::funds(succeeded).is_none());
+		// The fund account endowment is returned to the creator
+		assert_eq!(Balances::free_balance(AprilDaoModule::fund_account_id(succeeded)), 0);
+		assert_eq!(Balances::free_balance(1), 999);
+	});
+}
+
+#[test]
+fn minted_nft_uri_is_derived_from_collection_metadata() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_ok!(AprilDaoModule::set_item_metadata(
+			Origin::signed(1),
+			collection_id,
+			1,
+			b"ipfs://item-one".to_vec()
+		));
+		// The metadata deposit is 2 plus 1 per byte of the URI
+		assert_eq!(Balances::reserved_balance(1), 9 + 17);
+		assert_ok!(AprilDaoModule::set_item_metadata(
+			Origin::signed(1),
+			collection_id,
+			1,
+			b"ipfs://item-1".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(1), 9 + 15);
+		assert_ok!(AprilDaoModule::approve_collection(
+			Origin::root(),
+			collection_id,
+			0,
+			100,
+			SaleWindowUnit::BlockNumber
+		));
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+
+		assert_eq!(
+			AprilDaoModule::get_nfts(collection_id, 0).image_url.into_inner(),
+			b"ipfs://base/0".to_vec()
+		);
+		assert_eq!(
+			AprilDaoModule::get_nfts(collection_id, 1).image_url.into_inner(),
+			b"ipfs://item-1".to_vec()
+		);
+		// The deposit is returned once the URI is moved to the minted item
+		assert!(AprilDaoModule::item_metadata(collection_id, 1).is_none());
+		assert_eq!(Balances::reserved_balance(1), 0);
+	});
+}
+
+#[test]
+fn item_metadata_is_locked_after_approval() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_noop!(
+			AprilDaoModule::set_item_metadata(Origin::signed(1), collection_id, 5, b"uri".to_vec()),
+			Error::<Test>::InvalidItemIndex
+		);
+		assert_ok!(AprilDaoModule::approve_collection(
+			Origin::root(),
+			collection_id,
+			0,
+			100,
+			SaleWindowUnit::BlockNumber
+		));
+		assert_noop!(
+			AprilDaoModule::set_item_metadata(Origin::signed(1), collection_id, 0, b"uri".to_vec()),
+			Error::<Test>::CollectionNotInDraftState
+		);
+	});
+}
+
+#[test]
+fn update_metadata_works() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_noop!(
+			AprilDaoModule::update_metadata(
+				Origin::signed(2),
+				collection_id,
+				b"ipfs://new/".to_vec()
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			AprilDaoModule::update_metadata(Origin::signed(1), collection_id, vec![0; 33]),
+			Error::<Test>::UriTooLong
+		);
+		assert_ok!(AprilDaoModule::update_metadata(
+			Origin::signed(1),
+			collection_id,
+			b"ipfs://new/".to_vec()
+		));
+		assert_eq!(
+			AprilDaoModule::get_collections(collection_id).unwrap().base_uri.into_inner(),
+			b"ipfs://new/".to_vec()
+		);
+	});
+}
+
+#[test]
+fn register_collection_rejects_oversized_fields() {
+	new_test_ext().execute_with(|| {
+		let register = |name: Vec<u8>, description: Vec<u8>, base_uri: Vec<u8>| {
+			CollectionParams::default()
+				.name(&name)
+				.description(&description)
+				.base_uri(&base_uri)
+				.register()
+		};
+
+		assert_noop!(register(vec![0; 17], vec![], vec![]), Error::<Test>::NameTooLong);
+		assert_noop!(register(vec![], vec![0; 33], vec![]), Error::<Test>::DescriptionTooLong);
+		assert_noop!(register(vec![], vec![], vec![0; 33]), Error::<Test>::UriTooLong);
+	});
+}
+
+#[test]
+fn collection_names_are_unique_by_slug() {
+	new_test_ext().execute_with(|| {
+		let register = |owner: u128, name: &[u8]| {
+			CollectionParams::default().owner(owner).name(name).register()
+		};
+
+		assert_ok!(register(1, b"My  Collection!"));
+		let collection = AprilDaoModule::get_collections(0).unwrap();
+		assert_eq!(collection.slug.into_inner(), b"my-collection".to_vec());
+		assert_eq!(AprilDaoModule::collection_by_slug(b"my-collection".to_vec()), Some(0));
+		assert_eq!(AprilDaoModule::collection_by_slug(b"My Collection".to_vec()), Some(0));
+		assert_eq!(AprilDaoModule::collection_by_slug(b"other".to_vec()), None);
+
+		assert_noop!(register(2, b"my collection"), Error::<Test>::CollectionNameTaken);
+		assert_noop!(register(2, b"-- !!"), Error::<Test>::InvalidCollectionName);
+
+		// The name is free again once the collection is cancelled
+		let witness = AprilDaoModule::cancel_witness(0).unwrap();
+		assert_ok!(AprilDaoModule::cancel_collection(Origin::signed(1), 0, witness));
+		assert_eq!(AprilDaoModule::collection_by_slug(b"my-collection".to_vec()), None);
+		assert_ok!(register(2, b"My Collection"));
+		assert_eq!(AprilDaoModule::collection_by_slug(b"my-collection".to_vec()), Some(1));
+	});
+}
+
+#[test]
+fn non_ascii_collection_names_keep_their_characters() {
+	new_test_ext().execute_with(|| {
+		let register = |name: &str| CollectionParams::default().name(name.as_bytes()).register();
+
+		assert_ok!(register("Café"));
+		assert_eq!(
+			AprilDaoModule::get_collections(0).unwrap().slug.into_inner(),
+			"café".as_bytes()
+		);
+		assert_noop!(register("café"), Error::<Test>::CollectionNameTaken);
+		assert_ok!(register("Cafe"));
+
+		assert_ok!(register("東京 アート"));
+		assert_eq!(
+			AprilDaoModule::get_collections(2).unwrap().slug.into_inner(),
+			"東京-アート".as_bytes()
+		);
+		assert_eq!(AprilDaoModule::collection_by_slug("東京 アート".as_bytes().to_vec()), Some(2));
+	});
+}
+
+#[test]
+fn rejected_collection_frees_its_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollectionParams::default().name(b"Spam").register());
+		assert_ok!(AprilDaoModule::reject_collection(Origin::root(), 0, b"Spam".to_vec(), true));
+
+		assert_eq!(AprilDaoModule::collection_by_slug(b"spam".to_vec()), None);
+		assert!(AprilDaoModule::get_collections(0).unwrap().slug.is_empty());
+		assert_ok!(CollectionParams::default().owner(2).name(b"Spam").register());
+		assert_eq!(AprilDaoModule::collection_by_slug(b"spam".to_vec()), Some(1));
+	});
+}
+
+#[test]
+fn rejecting_an_unindexed_duplicate_keeps_the_original_name() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(CollectionParams::default().name(b"Spam").register());
+		// A collection migrated with a duplicate name is not indexed
+		let duplicate = CollectionParams::default().name(b"Other").create();
+		crate::Collections::<Test>::mutate(duplicate, |collection| {
+			collection.as_mut().unwrap().slug = b"spam".to_vec().try_into().unwrap();
+		});
+
+		assert_ok!(AprilDaoModule::reject_collection(
+			Origin::root(),
+			duplicate,
+			b"Spam".to_vec(),
+			false
+		));
+		assert_eq!(AprilDaoModule::collection_by_slug(b"spam".to_vec()), Some(0));
+	});
+}
+
+#[test]
+fn base_uri_leaves_room_for_the_item_ids() {
+	new_test_ext().execute_with(|| {
+		// The URI of item 4 is one character longer than the base URI
+		assert_noop!(
+			CollectionParams::default().base_uri(&[b'a'; 32]).register(),
+			Error::<Test>::UriTooLong
+		);
+		assert_noop!(
+			CollectionParams::default().base_uri(&[b'a'; 31]).number_of_items(11).register(),
+			Error::<Test>::UriTooLong
+		);
+		let collection_id =
+			CollectionParams::default().base_uri(&[b'a'; 31]).create_approved(0, 100);
+		assert_noop!(
+			AprilDaoModule::update_metadata(Origin::signed(1), collection_id, vec![b'b'; 32]),
+			Error::<Test>::UriTooLong
+		);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 3));
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 2));
+		let mut uri = vec![b'a'; 31];
+		uri.push(b'4');
+		assert_eq!(AprilDaoModule::get_nfts(collection_id, 4).image_url.into_inner(), uri);
+	});
+}
+
+#[test]
+fn approve_collection_requires_approve_origin() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_noop!(
+			AprilDaoModule::approve_collection(
+				Origin::signed(1),
+				collection_id,
+				0,
+				100,
+				SaleWindowUnit::BlockNumber
+			),
+			BadOrigin
+		);
+
+		assert_ok!(AprilDaoModule::approve_collection(
+			Origin::root(),
+			collection_id,
+			0,
+			100,
+			SaleWindowUnit::BlockNumber
+		));
+		System::assert_last_event(CollectionEvent::CollectionApproved(collection_id).into());
+		// The submission deposit is returned on approval, less the fund account endowment
+		assert_eq!(Balances::free_balance(1), 999);
+		assert_eq!(Balances::reserved_balance(1), 0);
+
+		assert_noop!(
+			AprilDaoModule::approve_collection(
+				Origin::root(),
+				collection_id,
+				0,
+				100,
+				SaleWindowUnit::BlockNumber
+			),
+			Error::<Test>::CollectionNotInDraftState
+		);
+	});
+}
+
+#[test]
+fn reject_collection_slashes_deposit() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_eq!(Balances::free_balance(1), 990);
+		assert_eq!(Balances::reserved_balance(1), 9);
+
+		assert_ok!(AprilDaoModule::reject_collection(
+			Origin::root(),
+			collection_id,
+			b"Spam".to_vec(),
+			true
+		));
+
+		let collection = AprilDaoModule::get_collections(collection_id).unwrap();
+		assert_eq!(collection.project_status, ProjectStatus::Rejected);
+		assert_eq!(AprilDaoModule::rejection_reason(collection_id).unwrap().into_inner(), b"Spam");
+		// Only the fund account endowment is returned
+		assert_eq!(Balances::free_balance(1), 991);
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::total_issuance(), 3_991);
+		assert!(AprilDaoModule::funds(collection_id).is_none());
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::CollectionIsInDraftState
+		);
+	});
+}
+
+#[test]
+fn reject_collection_can_return_deposit() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+
+		assert_ok!(AprilDaoModule::reject_collection(
+			Origin::root(),
+			collection_id,
+			b"Incomplete".to_vec(),
+			false
+		));
+
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_noop!(
+			AprilDaoModule::approve_collection(
+				Origin::root(),
+				collection_id,
+				0,
+				100,
+				SaleWindowUnit::BlockNumber
+			),
+			Error::<Test>::CollectionNotInDraftState
+		);
+	});
+}
+
+#[test]
+fn approving_a_collection_without_fund_leaves_no_sale_window() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(<AprilDaoModule as nonfungibles::Create<u128>>::create_class(&0, &1, &1));
+
+		assert_noop!(
+			AprilDaoModule::approve_collection(
+				Origin::root(),
+				0,
+				0,
+				10,
+				SaleWindowUnit::BlockNumber
+			),
+			Error::<Test>::InvalidFundIndex
+		);
+		assert!(AprilDaoModule::sale_windows().is_empty());
+	});
+}
+
+fn reveal_metadata() -> Vec<Vec<u8>> {
+	(0..5).map(|i| format!("ipfs://final/{}", i).into_bytes()).collect()
+}
+
+fn setup_delayed_reveal() -> CollectionId {
+	let collection_id = CollectionParams::default().create();
+	assert_ok!(AprilDaoModule::commit_provenance(
+		Origin::signed(1),
+		collection_id,
+		BlakeTwo256::hash_of(&reveal_metadata())
+	));
+	assert_ok!(AprilDaoModule::approve_collection(
+		Origin::root(),
+		collection_id,
+		0,
+		10,
+		SaleWindowUnit::BlockNumber
+	));
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+	assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+	collection_id
+}
+
+#[test]
+fn delayed_reveal_mints_placeholders() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_delayed_reveal();
+
+		assert_eq!(AprilDaoModule::nfts_map(collection_id, 0).unwrap().image_url, b"ipfs://base/");
+		assert_eq!(AprilDaoModule::nfts_map(collection_id, 1).unwrap().image_url, b"ipfs://base/");
+		assert_noop!(
+			AprilDaoModule::commit_provenance(Origin::signed(1), collection_id, Default::default()),
+			Error::<Test>::CollectionNotInDraftState
+		);
+	});
+}
+
+#[test]
+fn reveal_assigns_committed_metadata_after_sale() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Hooks;
+		let collection_id = setup_delayed_reveal();
+
+		assert_noop!(
+			AprilDaoModule::reveal(Origin::signed(1), collection_id, reveal_metadata()),
+			Error::<Test>::SaleNotEnded
+		);
+
+		System::set_block_number(10);
+		AprilDaoModule::on_initialize(10);
+		let offset = AprilDaoModule::reveals(collection_id).unwrap().offset.unwrap();
+		System::assert_last_event(CollectionEvent::RevealOffsetDrawn(collection_id, offset).into());
+
+		let mut tampered = reveal_metadata();
+		tampered.swap(0, 1);
+		assert_noop!(
+			AprilDaoModule::reveal(Origin::signed(1), collection_id, tampered),
+			Error::<Test>::InvalidProvenance
+		);
+		assert_noop!(
+			AprilDaoModule::reveal(Origin::signed(2), collection_id, reveal_metadata()),
+			Error::<Test>::NoPermission
+		);
+
+		assert_ok!(AprilDaoModule::reveal(Origin::signed(1), collection_id, reveal_metadata()));
+
+		let metadata = reveal_metadata();
+		for nft_id in 0..2u32 {
+			let expected = &metadata[((nft_id + offset) % 5) as usize];
+			assert_eq!(
+				&AprilDaoModule::nfts_map(collection_id, nft_id).unwrap().image_url[..],
+				&expected[..]
+			);
+			assert_eq!(
+				&AprilDaoModule::get_nfts(collection_id, nft_id).image_url[..],
+				&expected[..]
+			);
+		}
+		assert_noop!(
+			AprilDaoModule::reveal(Origin::signed(1), collection_id, reveal_metadata()),
+			Error::<Test>::AlreadyRevealed
+		);
+	});
+}
+
+fn setup_redeemable_collection(goal: u64) -> CollectionId {
+	let collection_id = CollectionParams::default()
+		.goal(goal)
+		.fund_end(10)
+		.redeemable()
+		.create_approved(0, 10);
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+	assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+	collection_id
+}
+
+#[test]
+fn burn_destroys_nft() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_noop!(
+			AprilDaoModule::burn(Origin::signed(3), collection_id, 0),
+			Error::<Test>::NotNFTOwner
+		);
+		assert_ok!(AprilDaoModule::burn(Origin::signed(2), collection_id, 0));
+		System::assert_last_event(CollectionEvent::Burned(collection_id, 0, 2, 0).into());
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), None);
+		assert_eq!(AprilDaoModule::nfts_map(collection_id, 0), None);
+		assert!(AprilDaoModule::get_collection_owner_nft(collection_id, 2).is_empty());
+		let collection = AprilDaoModule::get_collections(collection_id).unwrap();
+		assert_eq!((collection.number_of_minted, collection.number_of_burned), (0, 1));
+
+		// Ids of burned items are not reused
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+		assert_eq!(AprilDaoModule::owner(collection_id, 1), Some(3));
+	});
+}
+
+#[test]
+fn burn_redeems_share_of_successful_fund() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_redeemable_collection(20);
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+
+		assert_noop!(
+			AprilDaoModule::burn(Origin::signed(2), collection_id, 0),
+			Error::<Test>::FundStillActive
+		);
+
+		System::set_block_number(11);
+		// The fund is held for the holders
+		assert_noop!(
+			AprilDaoModule::dispense_fund(Origin::signed(1), collection_id, 1),
+			Error::<Test>::FundRedeemable
+		);
+		assert_ok!(AprilDaoModule::burn(Origin::signed(2), collection_id, 0));
+		System::assert_last_event(CollectionEvent::Burned(collection_id, 0, 2, 10).into());
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Balances::free_balance(fund_account), 11);
+
+		assert_ok!(AprilDaoModule::burn(Origin::signed(3), collection_id, 1));
+		assert_eq!(Balances::free_balance(3), 1_000);
+		// Only the endowment is left
+		assert_eq!(Balances::free_balance(fund_account), 1);
+	});
+}
+
+#[test]
+fn burn_does_not_redeem_failed_fund() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_redeemable_collection(100);
+		System::set_block_number(11);
+
+		assert_noop!(
+			AprilDaoModule::burn(Origin::signed(2), collection_id, 0),
+			Error::<Test>::FundFailed
+		);
+	});
+}
+
+#[test]
+fn royalty_is_configurable_by_owner() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_eq!(AprilDaoModule::royalty_info(collection_id, 0, 1_000), None);
+
+		let royalty = Some(RoyaltyInfo { recipient: 4, rate: Perbill::from_percent(5) });
+		assert_noop!(
+			AprilDaoModule::set_royalty(Origin::signed(2), collection_id, royalty.clone()),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::set_royalty(Origin::signed(1), collection_id, royalty));
+		System::assert_last_event(CollectionEvent::RoyaltySet(collection_id).into());
+
+		assert_eq!(AprilDaoModule::royalty_info(collection_id, 0, 1_000), Some((4, 50)));
+	});
+}
+
+#[test]
+fn pay_royalty_charges_buyer() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+		assert_ok!(AprilDaoModule::set_royalty(
+			Origin::signed(1),
+			collection_id,
+			Some(RoyaltyInfo { recipient: 4, rate: Perbill::from_percent(10) })
+		));
+
+		assert_eq!(AprilDaoModule::pay_royalty(collection_id, 0, &3, 200), Ok(20));
+		System::assert_last_event(CollectionEvent::RoyaltyPaid(collection_id, 0, 4, 20).into());
+		assert_eq!(Balances::free_balance(3), 980);
+		assert_eq!(Balances::free_balance(4), 1_020);
+	});
+}
+
+fn setup_listed_nft(price: u64) -> CollectionId {
+	let collection_id = setup_minted_collection();
+	assert_ok!(AprilDaoModule::set_royalty(
+		Origin::signed(1),
+		collection_id,
+		Some(RoyaltyInfo { recipient: 1, rate: Perbill::from_percent(10) })
+	));
+	assert_ok!(AprilDaoModule::list_for_sale(Origin::signed(2), collection_id, 0, price));
+	collection_id
+}
+
+#[test]
+fn buy_pays_seller_and_royalty() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_listed_nft(100);
+		System::assert_last_event(CollectionEvent::Listed(collection_id, 0, 2, 100).into());
+
+		assert_noop!(
+			AprilDaoModule::buy(Origin::signed(2), collection_id, 0, 100),
+			Error::<Test>::BuyerIsSeller
+		);
+		assert_noop!(
+			AprilDaoModule::buy(Origin::signed(3), collection_id, 0, 99),
+			Error::<Test>::PriceTooHigh
+		);
+
+		let creator_balance = Balances::free_balance(1);
+		assert_ok!(AprilDaoModule::buy(Origin::signed(3), collection_id, 0, 100));
+		System::assert_last_event(CollectionEvent::Sold(collection_id, 0, 2, 3, 100).into());
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(3));
+		assert_eq!(AprilDaoModule::listings(collection_id, 0), None);
+		// The minter paid the mint fee of 10
+		assert_eq!(Balances::free_balance(2), 990 + 90);
+		assert_eq!(Balances::free_balance(3), 900);
+		assert_eq!(Balances::free_balance(1), creator_balance + 10);
+	});
+}
+
+#[test]
+fn listed_nft_is_locked_for_delegates() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_listed_nft(100);
+		assert_ok!(AprilDaoModule::approve(Origin::signed(2), collection_id, 0, 4));
+
+		assert_noop!(
+			AprilDaoModule::transfer_from(Origin::signed(4), collection_id, 0, 2, 4),
+			Error::<Test>::NFTListed
+		);
+		assert_noop!(
+			AprilDaoModule::cancel_listing(Origin::signed(3), collection_id, 0),
+			Error::<Test>::NotNFTOwner
+		);
+		assert_ok!(AprilDaoModule::cancel_listing(Origin::signed(2), collection_id, 0));
+		assert_noop!(
+			AprilDaoModule::buy(Origin::signed(3), collection_id, 0, 100),
+			Error::<Test>::NotListed
+		);
+	});
+}
+
+#[test]
+fn transfer_and_burn_clear_listing() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_listed_nft(100);
+
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3));
+		assert_eq!(AprilDaoModule::listings(collection_id, 0), None);
+
+		assert_ok!(AprilDaoModule::list_for_sale(Origin::signed(3), collection_id, 0, 50));
+		assert_ok!(AprilDaoModule::burn(Origin::signed(3), collection_id, 0));
+		assert_eq!(AprilDaoModule::listings(collection_id, 0), None);
+	});
+}
+
+#[test]
+fn dutch_auction_price_decays_to_floor() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Hooks;
+		let collection_id = CollectionParams::default()
+			.sale_mode(SaleMode::DutchAuction { start_price: 100, floor_price: 40, decay: 20 })
+			.create_approved(2, 10);
+
+		System::set_block_number(2);
+		AprilDaoModule::on_initialize(2);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		assert_eq!(Balances::free_balance(2), 900);
+
+		System::set_block_number(4);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+		assert_eq!(Balances::free_balance(3), 940);
+
+		System::set_block_number(9);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(4), collection_id, 1));
+		assert_eq!(Balances::free_balance(4), 960);
+		assert_eq!(AprilDaoModule::contribution_get(collection_id, &4), 40);
+	});
+}
+
+#[test]
+fn invalid_sale_modes_are_rejected() {
+	new_test_ext().execute_with(|| {
+		let register = |number_of_items: u32, sale_mode: SaleMode<u64>| {
+			CollectionParams::default()
+				.number_of_items(number_of_items)
+				.sale_mode(sale_mode)
+				.register()
+		};
+
+		assert_noop!(
+			register(5, SaleMode::DutchAuction { start_price: 10, floor_price: 20, decay: 1 }),
+			Error::<Test>::InvalidSaleMode
+		);
+		assert_noop!(
+			register(2, SaleMode::EnglishAuction { reserve_price: 10 }),
+			Error::<Test>::InvalidSaleMode
+		);
+	});
+}
+
+#[test]
+fn english_auction_settles_to_highest_bidder() {
+	new_test_ext().execute_with(|| {
+		use frame_support::traits::Hooks;
+		let collection_id = CollectionParams::default()
+			.number_of_items(1)
+			.sale_mode(SaleMode::EnglishAuction { reserve_price: 50 })
+			.create_approved(2, 10);
+
+		System::set_block_number(2);
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::AuctionOnly
+		);
+		assert_noop!(
+			AprilDaoModule::bid(Origin::signed(2), collection_id, 40),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(AprilDaoModule::bid(Origin::signed(2), collection_id, 60));
+		assert_eq!(Balances::reserved_balance(2), 60);
+		assert_noop!(
+			AprilDaoModule::bid(Origin::signed(3), collection_id, 60),
+			Error::<Test>::BidTooLow
+		);
+		assert_ok!(AprilDaoModule::bid(Origin::signed(3), collection_id, 70));
+		// The outbid bidder is refunded
+		assert_eq!(Balances::reserved_balance(2), 0);
+		assert_eq!(Balances::free_balance(2), 1_000);
+
+		System::set_block_number(10);
+		AprilDaoModule::on_initialize(10);
+		AprilDaoModule::on_finalize(10);
+		System::assert_last_event(CollectionEvent::AuctionSettled(collection_id, 0, 3, 70).into());
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(3));
+		assert_eq!(Balances::reserved_balance(3), 0);
+		assert_eq!(Balances::free_balance(3), 930);
+		assert_eq!(AprilDaoModule::contribution_get(collection_id, &3), 70);
+	});
+}
+
+fn allowlist_leaf(account: u128, quota: u32) -> H256 {
+	BlakeTwo256::hash_of(&(account, quota))
+}
+
+fn hash_pair(a: H256, b: H256) -> H256 {
+	if a <= b {
+		BlakeTwo256::hash_of(&(a, b))
+	} else {
+		BlakeTwo256::hash_of(&(b, a))
+	}
+}
+
+fn setup_presale() -> CollectionId {
+	let collection_id = CollectionParams::default().create();
+	let root = hash_pair(allowlist_leaf(2, 2), allowlist_leaf(3, 1));
+	assert_ok!(AprilDaoModule::set_presale(Origin::signed(1), collection_id, root, 5));
+	assert_ok!(AprilDaoModule::approve_collection(
+		Origin::root(),
+		collection_id,
+		0,
+		100,
+		SaleWindowUnit::BlockNumber
+	));
+	collection_id
+}
+
+#[test]
+fn auction_collections_have_no_presale() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default()
+			.number_of_items(1)
+			.sale_mode(SaleMode::EnglishAuction { reserve_price: 50 })
+			.create();
+		let root = hash_pair(allowlist_leaf(2, 1), allowlist_leaf(3, 1));
+
+		assert_noop!(
+			AprilDaoModule::set_presale(Origin::signed(1), collection_id, root, 5),
+			Error::<Test>::AuctionOnly
+		);
+	});
+}
+
+#[test]
+fn mint_allowlisted_verifies_proof_and_quota() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_presale();
+		let proof = vec![allowlist_leaf(3, 1)];
+
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::PublicSaleNotStarted
+		);
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(Origin::signed(2), collection_id, proof.clone(), 3, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(Origin::signed(4), collection_id, proof.clone(), 2, 1),
+			Error::<Test>::InvalidMerkleProof
+		);
+
+		assert_ok!(AprilDaoModule::mint_allowlisted(
+			Origin::signed(2),
+			collection_id,
+			proof.clone(),
+			2,
+			2
+		));
+		System::assert_last_event(CollectionEvent::AllowlistMinted(collection_id, 2, 2).into());
+		assert_eq!(AprilDaoModule::allowlist_claimed(collection_id, 2), 2);
+		assert_eq!(
+			AprilDaoModule::get_collection_owner_nft(collection_id, 2).into_inner(),
+			vec![0, 1]
+		);
+		// Presale price instead of the mint fee of 10
+		assert_eq!(Balances::free_balance(2), 990);
+
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(Origin::signed(2), collection_id, proof, 2, 1),
+			Error::<Test>::AllowlistQuotaExceeded
+		);
+		assert_ok!(AprilDaoModule::mint_allowlisted(
+			Origin::signed(3),
+			collection_id,
+			vec![allowlist_leaf(2, 2)],
+			1,
+			1
+		));
+	});
+}
+
+#[test]
+fn mint_allowlisted_bounds_quantity() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_presale();
+		let proof = vec![allowlist_leaf(3, 1)];
+
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(Origin::signed(2), collection_id, proof.clone(), 2, 0),
+			Error::<Test>::InvalidMintQuantity
+		);
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(Origin::signed(2), collection_id, proof, 2, 4),
+			Error::<Test>::InvalidMintQuantity
+		);
+	});
+}
+
+#[test]
+fn public_sale_follows_allowlist_phase() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_presale();
+
+		assert_noop!(
+			AprilDaoModule::start_public_sale(Origin::signed(2), collection_id),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::start_public_sale(Origin::signed(1), collection_id));
+		System::assert_last_event(
+			CollectionEvent::SalePhaseChanged(collection_id, SalePhase::Public).into(),
+		);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(4), collection_id, 1));
+		assert_noop!(
+			AprilDaoModule::mint_allowlisted(
+				Origin::signed(2),
+				collection_id,
+				vec![allowlist_leaf(3, 1)],
+				2,
+				1
+			),
+			Error::<Test>::NotInAllowlistPhase
+		);
+		assert_noop!(
+			AprilDaoModule::set_presale(Origin::signed(1), collection_id, H256::zero(), 5),
+			Error::<Test>::NotInAllowlistPhase
+		);
+	});
+}
+
+#[test]
+fn mint_batches_up_to_max_mint_per_tx() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create_approved(0, 100);
+
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 0),
+			Error::<Test>::InvalidMintQuantity
+		);
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 4),
+			Error::<Test>::InvalidMintQuantity
+		);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 3));
+		assert_eq!(
+			AprilDaoModule::get_collection_owner_nft(collection_id, 2).into_inner(),
+			vec![0, 1, 2]
+		);
+		assert_eq!(Balances::free_balance(2), 970);
+
+		// Only two items are left
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(3), collection_id, 3),
+			Error::<Test>::SoldOut
+		);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 2));
+		assert_eq!(AprilDaoModule::get_collections(collection_id).unwrap().number_of_minted, 5);
+		assert_eq!(Balances::free_balance(3), 980);
+	});
+}
+
+#[test]
+fn mint_respects_max_per_account() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().max_per_account(2).create_approved(0, 100);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 2),
+			Error::<Test>::MintLimitReached
+		);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		assert_eq!(AprilDaoModule::minted_per_account(collection_id, 2), 2);
+
+		// Transfers do not reset the limit
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3));
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(2), collection_id, 1),
+			Error::<Test>::MintLimitReached
+		);
+	});
+}
+
+#[test]
+fn mint_fails_once_sold_out() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create_approved(0, 100);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 3));
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 2));
+
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(4), collection_id, 1),
+			Error::<Test>::SoldOut
+		);
+		assert_eq!(Balances::free_balance(4), 1_000);
+	});
+}
+
+#[test]
+fn mint_requires_the_whole_batch_to_be_paid() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create_approved(0, 100);
+		assert_ok!(Balances::set_balance(Origin::root(), 5, 25, 0));
+
+		assert_noop!(
+			AprilDaoModule::mint(Origin::signed(5), collection_id, 3),
+			Error::<Test>::InsufficientBalance
+		);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(5), collection_id, 2));
+		assert_eq!(
+			AprilDaoModule::get_collection_owner_nft(collection_id, 5).into_inner(),
+			vec![0, 1]
+		);
+		assert_eq!(Balances::free_balance(5), 5);
+	});
+}
+
+#[test]
+fn register_collection_fails_without_submission_deposit() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			CollectionParams::default().owner(5).register(),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(AprilDaoModule::fund_count(), 0);
+	});
+}
+
+fn setup_asset_collection(goal: u64) -> CollectionId {
+	let collection_id = CollectionParams::default()
+		.goal(goal)
+		.fund_end(10)
+		.payment_asset(PaymentAsset::Asset(0))
+		.create_approved(0, 10);
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 2));
+	collection_id
+}
+
+#[test]
+fn mint_fee_is_paid_in_collection_asset() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_asset_collection(20);
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+
+		assert_eq!(Assets::balance(0, 2), 980);
+		assert_eq!(Assets::balance(0, fund_account), 20);
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(AprilDaoModule::contribution_get(collection_id, &2), 20);
+
+		System::set_block_number(11);
+		assert_ok!(AprilDaoModule::dispense_fund(Origin::signed(1), collection_id, 4));
+		assert_eq!(Assets::balance(0, 4), 20);
+		assert_eq!(Assets::balance(0, fund_account), 0);
+	});
+}
+
+#[test]
+fn failed_asset_fund_refunds_in_asset() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_asset_collection(100);
+
+		System::set_block_number(11);
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(2), collection_id));
+		assert_eq!(Assets::balance(0, 2), 1_000);
+	});
+}
+
+#[test]
+fn asset_refunds_do_not_pay_out_the_dust_of_other_contributions() {
+	new_test_ext().execute_with(|| {
+		assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 15));
+		assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 1_000));
+		assert_ok!(Assets::mint(Origin::signed(1), 1, 3, 1_000));
+		let collection_id = CollectionParams::default()
+			.goal(100)
+			.fund_end(10)
+			.payment_asset(PaymentAsset::Asset(1))
+			.create_approved(0, 10);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 2));
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+
+		System::set_block_number(11);
+		// The 10 left for account 3 are below the minimum balance of the asset
+		assert_noop!(
+			AprilDaoModule::withdraw(Origin::signed(2), collection_id),
+			pallet_assets::Error::<Test>::BalanceLow
+		);
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(3), collection_id));
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(2), collection_id));
+		assert_eq!(Assets::balance(1, 2), 1_000);
+		assert_eq!(Assets::balance(1, 3), 1_000);
+	});
+}
+
+#[test]
+fn protocol_fee_is_split_from_contributions() {
+	new_test_ext().execute_with(|| {
+		set_protocol_fee(Perbill::from_percent(10));
+		let collection_id = setup_minted_collection();
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+		let fund = AprilDaoModule::funds(collection_id).unwrap();
+
+		assert_eq!(Balances::free_balance(2), 990);
+		assert_eq!(Balances::free_balance(99), 1);
+		assert_eq!(Balances::free_balance(fund_account), 10);
+		assert_eq!(fund.raised, 9);
+		assert_eq!(fund.fees, 1);
+		assert_eq!(AprilDaoModule::contribution_get(collection_id, &2), 9);
+		System::assert_has_event(CollectionEvent::ProtocolFeePaid(2, collection_id, 1).into());
+	});
+}
+
+#[test]
+fn protocol_fee_below_the_existential_deposit_does_not_fail_the_mint() {
+	new_test_ext().execute_with(|| {
+		set_existential_deposit(50);
+		set_protocol_fee(Perbill::from_percent(10));
+		let collection_id = CollectionParams::default().create_approved(0, 100);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		System::assert_has_event(CollectionEvent::ProtocolFeePaid(2, collection_id, 1).into());
+		assert_eq!(Balances::free_balance(2), 990);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 9);
+		// The fee handler could not endow account 99 with the fee, which is burned
+		assert_eq!(Balances::free_balance(99), 0);
+		assert_eq!(Balances::total_issuance(), 3_999);
+	});
+}
+
+#[test]
+fn protocol_fee_is_paid_in_collection_asset() {
+	new_test_ext().execute_with(|| {
+		set_protocol_fee(Perbill::from_percent(10));
+		let collection_id = setup_asset_collection(18);
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+
+		assert_eq!(Assets::balance(0, 2), 980);
+		assert_eq!(Assets::balance(0, 99), 2);
+		assert_eq!(Assets::balance(0, fund_account), 18);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().fees, 2);
+		assert_eq!(Balances::free_balance(99), 0);
+	});
+}
+
+#[test]
+fn asset_protocol_fee_below_the_minimum_balance_does_not_fail_the_mint() {
+	new_test_ext().execute_with(|| {
+		set_protocol_fee(Perbill::from_percent(10));
+		assert_ok!(Assets::force_create(Origin::root(), 1, 1, true, 5));
+		assert_ok!(Assets::mint(Origin::signed(1), 1, 2, 1_000));
+		let collection_id = CollectionParams::default()
+			.payment_asset(PaymentAsset::Asset(1))
+			.create_approved(0, 100);
+
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		// The fee of 1 can not endow account 99 and is burned
+		assert_eq!(Assets::balance(1, 99), 0);
+		assert_eq!(Assets::total_supply(1), 999);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 9);
+	});
+}
+
+#[test]
+fn frozen_collection_blocks_transfers_and_metadata() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_noop!(
+			AprilDaoModule::freeze_collection(Origin::signed(2), collection_id),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::freeze_collection(Origin::signed(1), collection_id));
+		System::assert_last_event(CollectionEvent::CollectionFrozen(collection_id).into());
+
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3),
+			Error::<Test>::CollectionFrozen
+		);
+		assert_noop!(
+			AprilDaoModule::update_metadata(
+				Origin::signed(1),
+				collection_id,
+				b"ipfs://new/".to_vec()
+			),
+			Error::<Test>::CollectionFrozen
+		);
+
+		assert_ok!(AprilDaoModule::thaw_collection(Origin::signed(1), collection_id));
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3));
+		assert_noop!(
+			AprilDaoModule::thaw_collection(Origin::signed(1), collection_id),
+			Error::<Test>::NotFrozen
+		);
+	});
+}
+
+#[test]
+fn frozen_item_can_not_be_transferred() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		assert_ok!(AprilDaoModule::freeze(Origin::signed(1), collection_id, 0));
+		System::assert_last_event(CollectionEvent::ItemFrozen(collection_id, 0).into());
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3),
+			Error::<Test>::ItemFrozen
+		);
+		assert_noop!(
+			AprilDaoModule::list_for_sale(Origin::signed(2), collection_id, 0, 10),
+			Error::<Test>::ItemFrozen
+		);
+
+		assert_ok!(AprilDaoModule::thaw(Origin::signed(1), collection_id, 0));
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3));
+	});
+}
+
+#[test]
+fn soulbound_items_are_never_transferable() {
+	new_test_ext().execute_with(|| {
+		let collection_id =
+			CollectionParams::default().name(b"Badge").soulbound().create_approved(0, 100);
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3),
+			Error::<Test>::Soulbound
+		);
+		assert_ok!(AprilDaoModule::approve(Origin::signed(2), collection_id, 0, 3));
+		assert_noop!(
+			AprilDaoModule::transfer_from(Origin::signed(3), collection_id, 0, 2, 3),
+			Error::<Test>::Soulbound
+		);
+		// Badges can still be given up
+		assert_ok!(AprilDaoModule::burn(Origin::signed(2), collection_id, 0));
+	});
+}
+
+#[test]
+fn collection_ownership_moves_once_accepted() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+
+		assert_noop!(
+			AprilDaoModule::transfer_collection_ownership(Origin::signed(2), collection_id, 2),
+			Error::<Test>::NotFundOwner
+		);
+		assert_ok!(AprilDaoModule::transfer_collection_ownership(
+			Origin::signed(1),
+			collection_id,
+			2
+		));
+		assert_eq!(AprilDaoModule::get_collections(collection_id).unwrap().owner, 1);
+		assert_noop!(
+			AprilDaoModule::accept_collection_ownership(Origin::signed(3), collection_id),
+			Error::<Test>::NotOfferedOwnership
+		);
+
+		assert_ok!(AprilDaoModule::accept_collection_ownership(Origin::signed(2), collection_id));
+
+		assert_eq!(AprilDaoModule::get_collections(collection_id).unwrap().owner, 2);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().beneficiary, 2);
+		assert_eq!(AprilDaoModule::ownership_acceptance(collection_id), None);
+		System::assert_last_event(CollectionEvent::OwnershipTransferred(collection_id, 2).into());
+		// The submission deposit moved from the old owner to the new one
+		assert_eq!((Balances::free_balance(1), Balances::reserved_balance(1)), (999, 0));
+		assert_eq!((Balances::free_balance(2), Balances::reserved_balance(2)), (991, 9));
+	});
+}
+
+#[test]
+fn accepting_ownership_requires_the_submission_deposit() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_ok!(AprilDaoModule::transfer_collection_ownership(
+			Origin::signed(1),
+			collection_id,
+			2
+		));
+		let _ = Balances::make_free_balance_be(&2, 5);
+
+		assert_noop!(
+			AprilDaoModule::accept_collection_ownership(Origin::signed(2), collection_id),
+			pallet_balances::Error::<Test>::InsufficientBalance
+		);
+		assert_eq!(Balances::reserved_balance(1), 9);
+	});
+}
+
+#[test]
+fn offering_ownership_to_the_owner_cancels_the_offer() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+
+		assert_ok!(AprilDaoModule::transfer_collection_ownership(
+			Origin::signed(1),
+			collection_id,
+			2
+		));
+		assert_ok!(AprilDaoModule::transfer_collection_ownership(
+			Origin::signed(1),
+			collection_id,
+			1
+		));
+
+		assert_noop!(
+			AprilDaoModule::accept_collection_ownership(Origin::signed(2), collection_id),
+			Error::<Test>::NotOfferedOwnership
+		);
+	});
+}
+
+#[test]
+fn collection_roles_gate_their_calls() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+
+		assert_noop!(
+			AprilDaoModule::set_team(Origin::signed(2), collection_id, 2, 3, 4),
+			Error::<Test>::NotFundOwner
+		);
+		assert_ok!(AprilDaoModule::set_team(Origin::signed(1), collection_id, 2, 3, 4));
+
+		// The owner no longer holds the roles it handed out
+		assert_noop!(
+			AprilDaoModule::update_metadata(
+				Origin::signed(1),
+				collection_id,
+				b"ipfs://new/".to_vec()
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::update_metadata(
+			Origin::signed(3),
+			collection_id,
+			b"ipfs://new/".to_vec()
+		));
+
+		assert_noop!(
+			AprilDaoModule::freeze_collection(Origin::signed(3), collection_id),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::freeze_collection(Origin::signed(4), collection_id));
+		assert_noop!(
+			AprilDaoModule::thaw_collection(Origin::signed(4), collection_id),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::thaw_collection(Origin::signed(3), collection_id));
+
+		assert_noop!(
+			AprilDaoModule::set_presale(Origin::signed(1), collection_id, H256::zero(), 5),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::set_presale(Origin::signed(2), collection_id, H256::zero(), 5));
+	});
+}
+
+#[test]
+fn nonfungibles_inspect_reads_collections_and_items() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = setup_minted_collection();
+
+		assert_eq!(<Nfts as nonfungibles::Inspect<u128>>::owner(&collection_id, &0), Some(2));
+		assert_eq!(<Nfts as nonfungibles::Inspect<u128>>::class_owner(&collection_id), Some(1));
+		assert!(<Nfts as nonfungibles::Inspect<u128>>::can_transfer(&collection_id, &0));
+
+		assert_ok!(AprilDaoModule::freeze(Origin::signed(1), collection_id, 0));
+		assert!(!<Nfts as nonfungibles::Inspect<u128>>::can_transfer(&collection_id, &0));
+	});
+}
+
+#[test]
+fn nonfungibles_inspect_enumerable_lists_items() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = setup_minted_collection();
+		assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+
+		assert_eq!(
+			<Nfts as nonfungibles::InspectEnumerable<u128>>::classes().collect::<Vec<_>>(),
+			vec![collection_id]
+		);
+		let mut items = <Nfts as nonfungibles::InspectEnumerable<u128>>::instances(&collection_id)
+			.collect::<Vec<_>>();
+		items.sort();
+		assert_eq!(items, vec![0, 1]);
+		assert_eq!(
+			<Nfts as nonfungibles::InspectEnumerable<u128>>::owned(&3).collect::<Vec<_>>(),
+			vec![(collection_id, 1)]
+		);
+		assert_eq!(
+			<Nfts as nonfungibles::InspectEnumerable<u128>>::owned_in_class(&collection_id, &2)
+				.collect::<Vec<_>>(),
+			vec![0]
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_create_mint_transfer_and_destroy() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id: CollectionId = AprilDaoModule::fund_count();
+
+		assert_noop!(
+			<Nfts as nonfungibles::Create<u128>>::create_class(&(collection_id + 1), &1, &2),
+			Error::<Test>::InvalidCollectionId
+		);
+		assert_ok!(<Nfts as nonfungibles::Create<u128>>::create_class(&collection_id, &1, &2));
+		let collection = AprilDaoModule::get_collections(collection_id).unwrap();
+		assert_eq!((collection.owner, collection.admin), (1, 2));
+		assert!(AprilDaoModule::funds(collection_id).is_none());
+
+		assert_noop!(
+			<Nfts as nonfungibles::Mutate<u128>>::mint_into(&collection_id, &1, &3),
+			Error::<Test>::InvalidItemIndex
+		);
+		assert_ok!(<Nfts as nonfungibles::Mutate<u128>>::mint_into(&collection_id, &0, &3));
+		assert_ok!(<Nfts as nonfungibles::Mutate<u128>>::set_attribute(
+			&collection_id,
+			&0,
+			b"level",
+			b"1"
+		));
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::attribute(&collection_id, &0, b"level"),
+			Some(b"1".to_vec())
+		);
+
+		assert_ok!(<Nfts as nonfungibles::Transfer<u128>>::transfer(&collection_id, &0, &4));
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(4));
+
+		let witness =
+			<Nfts as nonfungibles::Destroy<u128>>::get_destroy_witness(&collection_id).unwrap();
+		assert_eq!(witness, DestroyWitness { items: 1 });
+		assert_noop!(
+			<Nfts as nonfungibles::Destroy<u128>>::destroy(collection_id, witness, Some(2)),
+			Error::<Test>::NoPermission
+		);
+		assert_noop!(
+			<Nfts as nonfungibles::Destroy<u128>>::destroy(
+				collection_id,
+				DestroyWitness { items: 0 },
+				Some(1)
+			),
+			Error::<Test>::BadWitness
+		);
+		assert_ok!(<Nfts as nonfungibles::Destroy<u128>>::destroy(collection_id, witness, Some(1)));
+
+		assert!(AprilDaoModule::get_collections(collection_id).is_none());
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), None);
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::attribute(&collection_id, &0, b"level"),
+			None
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_destroy_requires_the_fund_to_be_closed() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = CollectionParams::default().create();
+
+		assert_noop!(
+			<Nfts as nonfungibles::Destroy<u128>>::destroy(
+				collection_id,
+				DestroyWitness { items: 0 },
+				None
+			),
+			Error::<Test>::FundExists
+		);
+	});
+}
+
+#[test]
+fn nonfungibles_burn_from_removes_the_item() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = setup_minted_collection();
+
+		assert_ok!(<Nfts as nonfungibles::Mutate<u128>>::burn_from(&collection_id, &0));
+
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), None);
+		assert_eq!(AprilDaoModule::get_collections(collection_id).unwrap().number_of_burned, 1);
+	});
+}
+
+#[test]
+fn set_attribute_reserves_a_deposit_from_the_admin() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = CollectionParams::default().create();
+
+		assert_noop!(
+			AprilDaoModule::set_attribute(
+				Origin::signed(2),
+				collection_id,
+				None,
+				b"rarity".to_vec(),
+				b"legendary".to_vec()
+			),
+			Error::<Test>::NoPermission
+		);
+		assert_ok!(AprilDaoModule::set_attribute(
+			Origin::signed(1),
+			collection_id,
+			None,
+			b"rarity".to_vec(),
+			b"legendary".to_vec()
+		));
+		// The submission deposit of 9 left after the endowment, plus the attribute base deposit of
+		// 5 and one per byte of key and value
+		assert_eq!(Balances::reserved_balance(1), 29);
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::class_attribute(&collection_id, b"rarity"),
+			Some(b"legendary".to_vec())
+		);
+
+		assert_ok!(AprilDaoModule::set_attribute(
+			Origin::signed(1),
+			collection_id,
+			None,
+			b"rarity".to_vec(),
+			b"rare".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(1), 24);
+
+		assert_ok!(AprilDaoModule::clear_attribute(
+			Origin::signed(1),
+			collection_id,
+			None,
+			b"rarity".to_vec()
+		));
+		assert_eq!(Balances::reserved_balance(1), 9);
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::class_attribute(&collection_id, b"rarity"),
+			None
+		);
+		assert_noop!(
+			AprilDaoModule::clear_attribute(
+				Origin::signed(1),
+				collection_id,
+				None,
+				b"rarity".to_vec()
+			),
+			Error::<Test>::NoAttribute
+		);
+	});
+}
+
+#[test]
+fn item_attributes_are_cleared_when_the_item_is_burned() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id = setup_minted_collection();
+
+		assert_noop!(
+			AprilDaoModule::set_attribute(
+				Origin::signed(1),
+				collection_id,
+				Some(5),
+				b"level".to_vec(),
+				b"3".to_vec()
+			),
+			Error::<Test>::InvalidItemIndex
+		);
+		assert_ok!(AprilDaoModule::set_attribute(
+			Origin::signed(1),
+			collection_id,
+			Some(0),
+			b"level".to_vec(),
+			b"3".to_vec()
+		));
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::attribute(&collection_id, &0, b"level"),
+			Some(b"3".to_vec())
+		);
+		assert_eq!(Balances::reserved_balance(1), 11);
+
+		assert_ok!(AprilDaoModule::burn(Origin::signed(2), collection_id, 0));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(
+			<Nfts as nonfungibles::Inspect<u128>>::attribute(&collection_id, &0, b"level"),
+			None
+		);
+	});
+}
+
+#[test]
+fn submission_deposit_is_reserved_apart_from_contributions() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+
+		// The fund account is endowed with the existential deposit out of the submission deposit
+		assert_eq!(Balances::reserved_balance(1), 9);
+		assert_eq!(Balances::free_balance(fund_account), 1);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 0);
+	});
+}
+
+#[test]
+fn contributions_below_the_existential_deposit_are_accepted() {
+	new_test_ext().execute_with(|| {
+		set_existential_deposit(50);
+		let collection_id = CollectionParams::default().goal(20).create_approved(0, 100);
+		let fund_account = AprilDaoModule::fund_account_id(collection_id);
+		assert_eq!(Balances::free_balance(fund_account), 50);
+		assert_eq!(Balances::free_balance(1), 950);
+
+		// The mint fee of 10 is below the existential deposit
+		assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+		assert_eq!(Balances::free_balance(fund_account), 60);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 10);
+
+		// Refunding the failed fund leaves the endowment behind
+		System::set_block_number(101);
+		assert_ok!(AprilDaoModule::withdraw(Origin::signed(2), collection_id));
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_eq!(Balances::free_balance(fund_account), 50);
+	});
+}
+
+#[test]
+fn cancel_collection_unreserves_deposit_and_removes_storage() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+		assert_ok!(AprilDaoModule::set_item_metadata(
+			Origin::signed(1),
+			collection_id,
+			0,
+			b"ipfs://item/0".to_vec()
+		));
+		assert_ok!(AprilDaoModule::set_attribute(
+			Origin::signed(1),
+			collection_id,
+			None,
+			b"tier".to_vec(),
+			b"gold".to_vec()
+		));
+
+		let witness = AprilDaoModule::cancel_witness(collection_id).unwrap();
+		assert_eq!(witness, CancelWitness { items: 0, item_metadata: 1, attributes: 1 });
+		assert_noop!(
+			AprilDaoModule::cancel_collection(Origin::signed(2), collection_id, witness),
+			Error::<Test>::NotFundOwner
+		);
+		// The witness must count the items and must not understate the metadata and attributes
+		for bad_witness in [
+			CancelWitness { items: 1, ..witness },
+			CancelWitness { item_metadata: 0, ..witness },
+			CancelWitness { attributes: 0, ..witness },
+		] {
+			assert_noop!(
+				AprilDaoModule::cancel_collection(Origin::signed(1), collection_id, bad_witness),
+				Error::<Test>::BadWitness
+			);
+		}
+		assert_ok!(AprilDaoModule::cancel_collection(Origin::signed(1), collection_id, witness));
+
+		System::assert_last_event(CollectionEvent::CollectionCancelled(collection_id).into());
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 1_000);
+		assert_eq!(Balances::free_balance(AprilDaoModule::fund_account_id(collection_id)), 0);
+		assert!(AprilDaoModule::get_collections(collection_id).is_none());
+		assert!(AprilDaoModule::funds(collection_id).is_none());
+		assert!(AprilDaoModule::item_metadata(collection_id, 0).is_none());
+	});
+}
+
+#[test]
+fn cancel_collection_requires_draft() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+
+		let witness = AprilDaoModule::cancel_witness(collection_id).unwrap();
+		assert_noop!(
+			AprilDaoModule::cancel_collection(Origin::signed(1), collection_id, witness),
+			Error::<Test>::CollectionNotInDraftState
+		);
+	});
+}
+
+#[test]
+fn accepting_ownership_moves_the_reserved_deposit() {
+	new_test_ext().execute_with(|| {
+		let collection_id = CollectionParams::default().create();
+
+		assert_ok!(AprilDaoModule::transfer_collection_ownership(
+			Origin::signed(1),
+			collection_id,
+			2
+		));
+		assert_ok!(AprilDaoModule::accept_collection_ownership(Origin::signed(2), collection_id));
+
+		assert_eq!(Balances::reserved_balance(1), 0);
+		assert_eq!(Balances::free_balance(1), 999);
+		assert_eq!(Balances::reserved_balance(2), 9);
+
+		let witness = AprilDaoModule::cancel_witness(collection_id).unwrap();
+		assert_ok!(AprilDaoModule::cancel_collection(Origin::signed(2), collection_id, witness));
+		// The fund account endowment goes to the owner of the collection
+		assert_eq!(Balances::free_balance(2), 1_001);
+	});
+}
+
+fn milestone(amount: u64, deadline: u64) -> Milestone<u64, H256, u64> {
+	Milestone { amount, description: H256::repeat_byte(amount as u8), deadline }
+}
+
+/// A collection with a goal of 20 and a fund ending at block 10, released in `milestones`.
+fn milestone_collection(milestones: Vec<Milestone<u64, H256, u64>>) -> CollectionParams {
+	CollectionParams::default().goal(20).fund_end(10).milestones(milestones)
+}
+
+/// A collection whose fund of 20 raised by items 0 (held by 2) and 1 (held by 3) is released in
+/// two milestones of 10, due by blocks 20 and 30.
+fn setup_milestone_collection() -> CollectionId {
+	let collection_id =
+		milestone_collection(vec![milestone(10, 20), milestone(10, 30)]).create_approved(0, 100);
+	assert_ok!(AprilDaoModule::mint(Origin::signed(2), collection_id, 1));
+	assert_ok!(AprilDaoModule::mint(Origin::signed(3), collection_id, 1));
+	System::set_block_number(11);
+	collection_id
+}
+
+#[test]
+fn register_collection_validates_milestones() {
+	new_test_ext().execute_with(|| {
+		assert_noop!(
+			milestone_collection(vec![milestone(10, 20)]).register(),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			milestone_collection(vec![milestone(10, 20), milestone(10, 20)]).register(),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			milestone_collection(vec![milestone(20, 10)]).register(),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			milestone_collection(vec![milestone(20, 20)]).redeemable().register(),
+			Error::<Test>::InvalidMilestones
+		);
+		assert_noop!(
+			milestone_collection(vec![
+				milestone(5, 20),
+				milestone(5, 30),
+				milestone(5, 40),
+				milestone(5, 50)
+			])
+			.register(),
+			Error::<Test>::TooManyMilestones
+		);
+
+		let collection_id = milestone_collection(vec![milestone(20, 20)]).create();
+		assert_eq!(AprilDaoModule::milestones(collection_id).into_inner(), vec![milestone(20, 20)]);
+	});
+}
+
+#[test]
+fn milestones_are_released_after_holder_approval() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_milestone_collection();
+
+		assert_noop!(
+			AprilDaoModule::dispense_fund(Origin::signed(1), collection_id, 1),
+			Error::<Test>::FundHasMilestones
+		);
+		assert_noop!(
+			AprilDaoModule::vote_milestone(Origin::signed(3), collection_id, 0, true),
+			Error::<Test>::NotNFTOwner
+		);
+
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, true));
+		assert_noop!(
+			AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, false),
+			Error::<Test>::AlreadyVoted
+		);
+		// Half of the items is not a majority
+		assert_noop!(
+			AprilDaoModule::release_milestone(Origin::signed(1), collection_id),
+			Error::<Test>::MilestoneNotApproved
+		);
+
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(3), collection_id, 1, true));
+		assert_ok!(AprilDaoModule::release_milestone(Origin::signed(1), collection_id));
+		System::assert_last_event(CollectionEvent::MilestoneReleased(collection_id, 0, 10).into());
+		assert_eq!(Balances::free_balance(1), 1_009);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 10);
+		assert_eq!(AprilDaoModule::milestone_progress(collection_id).current, 1);
+
+		// Votes are cast again for every milestone
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, true));
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(3), collection_id, 1, true));
+		assert_ok!(AprilDaoModule::release_milestone(Origin::signed(1), collection_id));
+
+		// The last release also returns the submission deposit and the fund account endowment
+		assert_eq!(Balances::free_balance(1), 1_020);
+		assert!(AprilDaoModule::funds(collection_id).is_none());
+		assert!(AprilDaoModule::milestones(collection_id).is_empty());
+		assert_noop!(
+			AprilDaoModule::release_milestone(Origin::signed(1), collection_id),
+			Error::<Test>::NoMilestone
+		);
+	});
+}
+
+#[test]
+fn rejected_milestone_refunds_unreleased_funds() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_milestone_collection();
+
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, false));
+		assert_noop!(
+			AprilDaoModule::claim_milestone_refund(Origin::signed(2), collection_id, 0),
+			Error::<Test>::MilestoneNotFailed
+		);
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(3), collection_id, 1, false));
+		assert_noop!(
+			AprilDaoModule::release_milestone(Origin::signed(1), collection_id),
+			Error::<Test>::MilestoneFailed
+		);
+
+		assert_ok!(AprilDaoModule::claim_milestone_refund(Origin::signed(2), collection_id, 0));
+		assert_eq!(Balances::free_balance(2), 1_000);
+		assert_noop!(
+			AprilDaoModule::claim_milestone_refund(Origin::signed(2), collection_id, 0),
+			Error::<Test>::AlreadyRefunded
+		);
+		assert_ok!(AprilDaoModule::claim_milestone_refund(Origin::signed(3), collection_id, 1));
+		assert_eq!(Balances::free_balance(3), 1_000);
+		assert_eq!(AprilDaoModule::funds(collection_id).unwrap().raised, 0);
+	});
+}
+
+#[test]
+fn missed_milestone_deadline_refunds_unreleased_funds() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_milestone_collection();
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, true));
+		assert_ok!(AprilDaoModule::vote_milestone(Origin::signed(3), collection_id, 1, true));
+		assert_ok!(AprilDaoModule::release_milestone(Origin::signed(1), collection_id));
+
+		System::set_block_number(31);
+		assert_noop!(
+			AprilDaoModule::vote_milestone(Origin::signed(2), collection_id, 0, true),
+			Error::<Test>::MilestoneFailed
+		);
+		assert_ok!(AprilDaoModule::claim_milestone_refund(Origin::signed(2), collection_id, 0));
+
+		System::assert_has_event(CollectionEvent::MilestoneFailed(collection_id, 1).into());
+		System::assert_last_event(CollectionEvent::MilestoneRefund(collection_id, 0, 2, 5).into());
+		assert_eq!(Balances::free_balance(2), 995);
+	});
+}
+
+#[test]
+fn fractionalized_item_is_redeemed_with_the_full_supply() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+		let account = AprilDaoModule::fraction_account_id(collection_id, 0);
+
+		assert_noop!(
+			AprilDaoModule::fractionalize(Origin::signed(2), collection_id, 0, 1, 0, None),
+			Error::<Test>::InvalidSupply
+		);
+		assert_noop!(
+			AprilDaoModule::fractionalize(Origin::signed(3), collection_id, 0, 1, 100, None),
+			Error::<Test>::NotNFTOwner
+		);
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(2),
+			collection_id,
+			0,
+			1,
+			100,
+			None
+		));
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(account));
+		assert_eq!(Assets::balance(1, 2), 100);
+		assert_noop!(
+			AprilDaoModule::transfer(Origin::signed(2), collection_id, 0, 3),
+			Error::<Test>::NotNFTOwner
+		);
+
+		assert_ok!(Assets::transfer(Origin::signed(2), 1, 3, 10));
+		assert_noop!(
+			AprilDaoModule::redeem(Origin::signed(2), collection_id, 0),
+			Error::<Test>::NotAllShares
+		);
+		assert_noop!(
+			AprilDaoModule::buyout(Origin::signed(4), collection_id, 0),
+			Error::<Test>::NoReservePrice
+		);
+
+		assert_ok!(Assets::transfer(Origin::signed(3), 1, 2, 10));
+		assert_ok!(AprilDaoModule::redeem(Origin::signed(2), collection_id, 0));
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(2));
+		assert_eq!(Assets::total_supply(1), 0);
+		assert_eq!(AprilDaoModule::fractions(collection_id, 0), None);
+		// The share token is destroyed, so its id can be used again
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(2),
+			collection_id,
+			0,
+			1,
+			100,
+			None
+		));
+	});
+}
+
+#[test]
+fn fractionalized_item_can_not_be_burned_or_destroyed() {
+	new_test_ext().execute_with(|| {
+		type Nfts = AprilDaoModule;
+		let collection_id: CollectionId = AprilDaoModule::fund_count();
+		assert_ok!(<Nfts as nonfungibles::Create<u128>>::create_class(&collection_id, &1, &1));
+		assert_ok!(<Nfts as nonfungibles::Mutate<u128>>::mint_into(&collection_id, &0, &2));
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(2),
+			collection_id,
+			0,
+			1,
+			100,
+			None
+		));
+
+		assert_noop!(
+			<Nfts as nonfungibles::Mutate<u128>>::burn_from(&collection_id, &0),
+			Error::<Test>::Fractionalized
+		);
+		assert_noop!(
+			<Nfts as nonfungibles::Destroy<u128>>::destroy(
+				collection_id,
+				DestroyWitness { items: 1 },
+				None
+			),
+			Error::<Test>::Fractionalized
+		);
+
+		// The item can be burned once redeemed
+		assert_ok!(AprilDaoModule::redeem(Origin::signed(2), collection_id, 0));
+		assert_ok!(<Nfts as nonfungibles::Mutate<u128>>::burn_from(&collection_id, &0));
+	});
+}
+
+#[test]
+fn bought_out_item_pays_shareholders_pro_rata() {
+	new_test_ext().execute_with(|| {
+		let collection_id = setup_minted_collection();
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(2),
+			collection_id,
+			0,
+			1,
+			100,
+			Some(300)
+		));
+		assert_ok!(Assets::transfer(Origin::signed(2), 1, 3, 30));
+		assert_noop!(
+			AprilDaoModule::claim_buyout(Origin::signed(3), collection_id, 0),
+			Error::<Test>::NotBoughtOut
+		);
+
+		assert_ok!(AprilDaoModule::buyout(Origin::signed(4), collection_id, 0));
+		assert_eq!(AprilDaoModule::owner(collection_id, 0), Some(4));
+		assert_eq!(Balances::free_balance(4), 700);
+		assert_noop!(
+			AprilDaoModule::buyout(Origin::signed(1), collection_id, 0),
+			Error::<Test>::AlreadyBoughtOut
+		);
+		assert_noop!(
+			AprilDaoModule::redeem(Origin::signed(2), collection_id, 0),
+			Error::<Test>::AlreadyBoughtOut
+		);
+
+		assert_ok!(AprilDaoModule::claim_buyout(Origin::signed(3), collection_id, 0));
+		assert_eq!(Balances::free_balance(3), 1_090);
+		assert_eq!(Assets::balance(1, 3), 0);
+		assert_noop!(
+			AprilDaoModule::claim_buyout(Origin::signed(3), collection_id, 0),
+			Error::<Test>::NoShares
+		);
+
+		assert_ok!(AprilDaoModule::claim_buyout(Origin::signed(2), collection_id, 0));
+		assert_eq!(Balances::free_balance(2), 1_200);
+		assert_eq!(AprilDaoModule::fractions(collection_id, 0), None);
+		System::assert_last_event(
+			CollectionEvent::BuyoutClaimed(collection_id, 0, 2, 70, 210).into(),
+		);
+	});
+}
+
+#[test]
+fn buyout_payouts_respect_the_existential_deposit() {
+	new_test_ext().execute_with(|| {
+		set_existential_deposit(50);
+		let collection_id = setup_minted_collection();
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(2),
+			collection_id,
+			0,
+			1,
+			100,
+			Some(300)
+		));
+		assert_ok!(Assets::transfer(Origin::signed(2), 1, 3, 10));
+		assert_ok!(AprilDaoModule::buyout(Origin::signed(4), collection_id, 0));
+
+		// 10 shares are worth 30, less than the existential deposit
+		assert_noop!(
+			AprilDaoModule::claim_buyout(Origin::signed(3), collection_id, 0),
+			Error::<Test>::PayoutTooSmall
+		);
+
+		// 90 shares are worth 270, but the existential deposit is left for the last shareholder
+		assert_ok!(AprilDaoModule::claim_buyout(Origin::signed(2), collection_id, 0));
+		System::assert_last_event(
+			CollectionEvent::BuyoutClaimed(collection_id, 0, 2, 90, 250).into(),
+		);
+		assert_ok!(AprilDaoModule::claim_buyout(Origin::signed(3), collection_id, 0));
+		System::assert_last_event(
+			CollectionEvent::BuyoutClaimed(collection_id, 0, 3, 10, 50).into(),
+		);
+		assert_eq!(Balances::free_balance(3), 1_050);
+
+		// The share token is destroyed, so its id can be used again
+		assert_eq!(AprilDaoModule::fractions(collection_id, 0), None);
+		assert_ok!(AprilDaoModule::fractionalize(
+			Origin::signed(4),
+			collection_id,
+			0,
+			1,
+			100,
+			None
+		));
+	});
+}
 
 #[test]
-fn it_works_for_default_value() {
+fn legacy_funds_end_with_their_sale_window() {
 	new_test_ext().execute_with(|| {
-		// Dispatch a signed extrinsic.
-		assert_ok!(AprilDaoModule::do_something(Origin::signed(1), 42));
-		// Read pallet storage and assert an expected result.
-		assert_eq!(AprilDaoModule::something(), Some(42));
+		let module = AprilDaoModule::name().as_bytes();
+		StorageVersion::new(2).put::<AprilDaoModule>();
+		for (collection_id, name, end_date) in [(0, b"Windowed", Some(20)), (1, b"Open-end", None)]
+		{
+			let collection = migrations::v2::CollectionInfo::<u128, u64> {
+				id: collection_id,
+				owner: 1,
+				name: name.to_vec(),
+				description: Vec::new(),
+				number_of_items: 10,
+				number_of_minted: 1,
+				is_frozen: false,
+				project_status: ProjectStatus::Approved,
+				mint_fee: 10,
+				start_date: Some(0),
+				end_date,
+				sale_window_unit: SaleWindowUnit::BlockNumber,
+			};
+			let fund = migrations::v0::FundInfo::<u128, u64, u64> {
+				beneficiary: 1,
+				deposit: 0,
+				raised: 10,
+				end: 1,
+			};
+			put_storage_value(
+				module,
+				b"Collections",
+				&Twox64Concat::hash(&collection_id.encode()),
+				collection,
+			);
+			put_storage_value(
+				module,
+				b"Funds",
+				&Blake2_128Concat::hash(&collection_id.encode()),
+				fund,
+			);
+			Balances::make_free_balance_be(&AprilDaoModule::fund_account_id(collection_id), 11);
+		}
+
+		AprilDaoModule::on_runtime_upgrade();
+		assert_eq!(
+			AprilDaoModule::on_chain_storage_version(),
+			AprilDaoModule::current_storage_version()
+		);
+		assert_eq!(AprilDaoModule::funds(0).unwrap().end, 20);
+		// Without a sale window the fund ends at the upgrade
+		assert_eq!(AprilDaoModule::funds(1).unwrap().end, 1);
+		assert_eq!(AprilDaoModule::funds(1).unwrap().goal, 0);
+
+		System::set_block_number(2);
+		assert_ok!(AprilDaoModule::dispense_fund(Origin::signed(1), 1, 4));
+		assert_eq!(Balances::free_balance(4), 1_010);
+		assert_noop!(
+			AprilDaoModule::dispense_fund(Origin::signed(1), 0, 4),
+			Error::<Test>::FundStillActive
+		);
+
+		System::set_block_number(21);
+		assert_ok!(AprilDaoModule::dispense_fund(Origin::signed(1), 0, 4));
+		assert_eq!(Balances::free_balance(4), 1_020);
+		// The endowments of both fund accounts went back to the creator
+		assert_eq!(Balances::free_balance(1), 1_002);
 	});
 }
 
 #[test]
-fn correct_error_for_none_value() {
+fn v0_items_are_migrated_to_collection_scoped_ownership() {
 	new_test_ext().execute_with(|| {
-		// Ensure the expected error is thrown when no value is present.
-		assert_noop!(AprilDaoModule::cause_error(Origin::signed(1)), Error::<Test>::NoneValue);
+		let module = AprilDaoModule::name().as_bytes();
+		StorageVersion::new(0).put::<AprilDaoModule>();
+		// Item 0 of both collections, keyed by the item id alone in `NFTOwned`
+		for (collection_id, name, owner) in [(0, b"Kitties", 2u128), (1, b"KITTIES", 3)] {
+			let collection_id: CollectionId = collection_id;
+			let collection = migrations::v0::CollectionInfo::<u128, u64> {
+				id: collection_id,
+				owner: 1,
+				name: name.to_vec(),
+				description: Vec::new(),
+				number_of_items: 5,
+				number_of_minted: 1,
+				is_frozen: false,
+				project_status: ProjectStatus::Approved,
+				mint_fee: 10,
+				start_date: Some(0),
+				end_date: Some(100),
+			};
+			put_storage_value(
+				module,
+				b"Collections",
+				&Twox64Concat::hash(&collection_id.encode()),
+				collection,
+			);
+			let nft = migrations::v0::NFT {
+				id: 0u16,
+				name: b"Item #0".to_vec(),
+				image_url: b"ipfs://kitty".to_vec(),
+			};
+			let mut item_key = Twox64Concat::hash(&collection_id.encode());
+			item_key.extend(Twox64Concat::hash(&0u16.encode()));
+			put_storage_value(module, b"NFTs", &item_key, &nft);
+			put_storage_value(module, b"NFTMap", &item_key, &nft);
+			put_storage_value(module, b"NFTOwned", &Twox64Concat::hash(&0u16.encode()), owner);
+			let mut owner_key = Twox64Concat::hash(&collection_id.encode());
+			owner_key.extend(Twox64Concat::hash(&owner.encode()));
+			put_storage_value(module, b"CollectionOwnerNFT", &owner_key, vec![0u16]);
+		}
+
+		AprilDaoModule::on_runtime_upgrade();
+		assert_eq!(
+			AprilDaoModule::on_chain_storage_version(),
+			AprilDaoModule::current_storage_version()
+		);
+
+		// v1: ownership is scoped by collection
+		assert_eq!(AprilDaoModule::owner(0, 0), Some(2));
+		assert_eq!(AprilDaoModule::owner(1, 0), Some(3));
+		assert_eq!(AprilDaoModule::get_collection_owner_nft(0, &2).into_inner(), vec![0]);
+		assert_eq!(AprilDaoModule::get_collection_owner_nft(1, &3).into_inner(), vec![0]);
+		assert_eq!(AprilDaoModule::get_nfts(1, 0).image_url.into_inner(), b"ipfs://kitty".to_vec());
+		assert_eq!(AprilDaoModule::nfts_map(0, 0).unwrap().id, 0);
+		// v2: open sale windows are scheduled
+		let mut windows = AprilDaoModule::sale_windows().into_inner();
+		windows.sort();
+		assert_eq!(windows, vec![(0, true), (1, true)]);
+		// v13 and v16: roles go to the owner and only one of two equal names is indexed
+		for collection_id in 0..2 {
+			let collection = AprilDaoModule::get_collections(collection_id).unwrap();
+			assert_eq!((collection.issuer, collection.admin, collection.freezer), (1, 1, 1));
+			assert_eq!(collection.slug.into_inner(), b"kitties".to_vec());
+		}
+		assert!(AprilDaoModule::collection_by_slug(b"kitties".to_vec()).is_some());
+
+		// The migrated items can be transferred within their collection
+		assert_ok!(AprilDaoModule::transfer(Origin::signed(3), 1, 0, 4));
+		assert_eq!(AprilDaoModule::owner(1, 0), Some(4));
+		assert_eq!(AprilDaoModule::owner(0, 0), Some(2));
 	});
 }
@@ -0,0 +1,144 @@
+//! Weights for pallet_collection.
+//!
+//! PLACEHOLDERS: these are NOT benchmark output. The constants are hand-written estimates from the
+//! storage accesses listed on each call and must be replaced by generated weights before the
+//! pallet goes to production. Generate them from the benchmarks in `benchmarking.rs` with the node
+//! `benchmark` command once the runtime is built with the `runtime-benchmarks` feature:
+//!
+//! ./target/release/node-april-dao benchmark --chain dev --execution wasm --wasm-execution compiled
+//! --pallet pallet_collection --extrinsic '*' --steps 50 --repeat 20
+//! --output pallets/collection/src/weights.rs
+
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use core::marker::PhantomData;
+use frame_support::{
+	traits::Get,
+	weights::{constants::RocksDbWeight, Weight},
+};
+
+/// Weight functions needed for pallet_collection.
+pub trait WeightInfo {
+	fn mint(q: u32) -> Weight;
+	fn mint_allowlisted(q: u32, p: u32) -> Weight;
+	fn cancel_collection(n: u32, m: u32, a: u32) -> Weight;
+}
+
+/// Placeholder weights for pallet_collection, estimated rather than benchmarked.
+pub struct SubstrateWeight<T>(PhantomData<T>);
+impl<T: frame_system::Config> WeightInfo for SubstrateWeight<T> {
+	// Storage: AprilDaoModule Collections (r:1 w:1)
+	// Storage: AprilDaoModule SaleOpenedAt (r:1 w:0)
+	// Storage: AprilDaoModule MintedPerAccount (r:1 w:1)
+	// Storage: AprilDaoModule ItemMetadata (r:1 w:1)
+	// Storage: AprilDaoModule Reveals (r:1 w:0)
+	// Storage: AprilDaoModule Funds (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: AprilDaoModule NFTs (r:0 w:1)
+	// Storage: AprilDaoModule NFTOwned (r:0 w:1)
+	// Storage: AprilDaoModule NFTMap (r:0 w:1)
+	// Storage: AprilDaoModule CollectionOwnerNFT (r:1 w:1)
+	// Storage: unknown child trie contribution (r:1 w:1)
+	fn mint(q: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((45_000_000 as Weight).saturating_mul(q as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(q as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(q as Weight)))
+	}
+	// Storage: AprilDaoModule Collections (r:1 w:1)
+	// Storage: AprilDaoModule SaleOpenedAt (r:1 w:0)
+	// Storage: AprilDaoModule AllowlistClaimed (r:1 w:1)
+	// Storage: AprilDaoModule Funds (r:1 w:1)
+	// Storage: AprilDaoModule MintedPerAccount (r:1 w:1)
+	// Storage: AprilDaoModule ItemMetadata (r:1 w:1)
+	// Storage: AprilDaoModule Reveals (r:1 w:0)
+	// Storage: System Account (r:2 w:2)
+	// Storage: AprilDaoModule NFTs (r:0 w:1)
+	// Storage: AprilDaoModule NFTOwned (r:0 w:1)
+	// Storage: AprilDaoModule NFTMap (r:0 w:1)
+	// Storage: AprilDaoModule CollectionOwnerNFT (r:1 w:1)
+	// Storage: unknown child trie contribution (r:1 w:1)
+	fn mint_allowlisted(q: u32, p: u32) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((45_000_000 as Weight).saturating_mul(q as Weight))
+			.saturating_add((1_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().reads((6 as Weight).saturating_mul(q as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((10 as Weight).saturating_mul(q as Weight)))
+	}
+	// Storage: AprilDaoModule Collections (r:1 w:1)
+	// Storage: AprilDaoModule ItemMetadata (r:1 w:1)
+	// Storage: AprilDaoModule Attributes (r:1 w:1)
+	// Storage: AprilDaoModule Fractions (r:1 w:0)
+	// Storage: AprilDaoModule Funds (r:1 w:1)
+	// Storage: System Account (r:2 w:2)
+	// Storage: AprilDaoModule CollectionByName (r:1 w:1)
+	// Storage: unknown child trie contribution (r:0 w:1)
+	// Storage: AprilDaoModule NFTs (r:0 w:1)
+	// Storage: AprilDaoModule NFTMap (r:0 w:1)
+	// Storage: AprilDaoModule NFTOwned (r:0 w:1)
+	// Storage: AprilDaoModule CollectionOwnerNFT (r:0 w:1)
+	// Storage: AprilDaoModule NFTApprovals (r:0 w:1)
+	// Storage: AprilDaoModule Listings (r:0 w:1)
+	// Storage: AprilDaoModule FrozenItems (r:0 w:1)
+	// Storage: System Account (r:1 w:1)
+	fn cancel_collection(n: u32, m: u32, a: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((5_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(
+				T::DbWeight::get()
+					.reads((2 as Weight).saturating_mul(m.saturating_add(a) as Weight)),
+			)
+			.saturating_add(T::DbWeight::get().writes(16 as Weight))
+			.saturating_add(T::DbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(
+				T::DbWeight::get()
+					.writes((2 as Weight).saturating_mul(m.saturating_add(a) as Weight)),
+			)
+	}
+}
+
+// For backwards compatibility and tests
+impl WeightInfo for () {
+	fn mint(q: u32) -> Weight {
+		(20_000_000 as Weight)
+			.saturating_add((45_000_000 as Weight).saturating_mul(q as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(q as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(q as Weight)))
+	}
+	fn mint_allowlisted(q: u32, p: u32) -> Weight {
+		(25_000_000 as Weight)
+			.saturating_add((45_000_000 as Weight).saturating_mul(q as Weight))
+			.saturating_add((1_000_000 as Weight).saturating_mul(p as Weight))
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((6 as Weight).saturating_mul(q as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((10 as Weight).saturating_mul(q as Weight)))
+	}
+	fn cancel_collection(n: u32, m: u32, a: u32) -> Weight {
+		(35_000_000 as Weight)
+			.saturating_add((10_000_000 as Weight).saturating_mul(n as Weight))
+			.saturating_add((2_000_000 as Weight).saturating_mul(m as Weight))
+			.saturating_add((5_000_000 as Weight).saturating_mul(a as Weight))
+			.saturating_add(RocksDbWeight::get().reads(7 as Weight))
+			.saturating_add(
+				RocksDbWeight::get()
+					.reads((2 as Weight).saturating_mul(m.saturating_add(a) as Weight)),
+			)
+			.saturating_add(RocksDbWeight::get().writes(16 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((7 as Weight).saturating_mul(n as Weight)))
+			.saturating_add(
+				RocksDbWeight::get()
+					.writes((2 as Weight).saturating_mul(m.saturating_add(a) as Weight)),
+			)
+	}
+}
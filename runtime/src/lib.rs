@@ -14,7 +14,10 @@ use sp_consensus_aura::sr25519::AuthorityId as AuraId;
 use sp_core::{crypto::KeyTypeId, OpaqueMetadata};
 use sp_runtime::{
 	create_runtime_str, generic, impl_opaque_keys,
-	traits::{AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount, NumberFor, Verify},
+	traits::{
+		AccountIdConversion, AccountIdLookup, BlakeTwo256, Block as BlockT, IdentifyAccount,
+		NumberFor, Verify,
+	},
 	transaction_validity::{TransactionSource, TransactionValidity},
 	ApplyExtrinsicResult, MultiSignature,
 };
@@ -29,14 +32,14 @@ pub use frame_support::{
 	pallet_prelude::Get,
 	parameter_types,
 	traits::{
-		ConstU128, ConstU32, ConstU8, KeyOwnerProofSystem, Randomness, ReservableCurrency,
-		StorageInfo,
+		tokens::fungibles, ConstU128, ConstU32, ConstU8, Currency, KeyOwnerProofSystem,
+		OnUnbalanced, Randomness, ReservableCurrency, StorageInfo,
 	},
 	weights::{
 		constants::{BlockExecutionWeight, ExtrinsicBaseWeight, RocksDbWeight, WEIGHT_PER_SECOND},
 		IdentityFee, Weight,
 	},
-	StorageValue,
+	PalletId, StorageValue,
 };
 pub use pallet_balances::Call as BalancesCall;
 pub use pallet_timestamp::Call as TimestampCall;
@@ -106,10 +109,10 @@ pub const VERSION: RuntimeVersion = RuntimeVersion {
 	//   `spec_version`, and `authoring_version` are the same between Wasm and native.
 	// This value is set to 100 to notify Polkadot-JS App (https://polkadot.js.org/apps) to use
 	//   the compatible custom types.
-	spec_version: 100,
+	spec_version: 101,
 	impl_version: 1,
 	apis: RUNTIME_API_VERSIONS,
-	transaction_version: 1,
+	transaction_version: 2,
 	state_version: 1,
 };
 
@@ -283,14 +286,67 @@ parameter_types! {
 	pub const SubmissionDeposit: u128 = 1 * 1_000_000_000;
 	// One can own at most 9,999 Kitties
 	pub const MaxNFTOwned: u32 = 100;
+	pub const MaxActiveSaleWindows: u32 = 100;
+	pub const UriLimit: u32 = 256;
+	pub const NameLimit: u32 = 64;
+	pub const DescriptionLimit: u32 = 1024;
+	pub const MaxMintPerTx: u32 = 10;
+	pub const KeyLimit: u32 = 32;
+	pub const ValueLimit: u32 = 256;
+	pub const MaxMilestones: u32 = 10;
+	pub const ProtocolFee: Perbill = Perbill::from_percent(2);
+	pub const LaunchpadTreasuryId: PalletId = PalletId(*b"ad/trsry");
+	pub LaunchpadTreasury: AccountId = LaunchpadTreasuryId::get().into_account();
+	pub const AttributeDepositBase: Balance = 1_000_000_000;
+	pub const DepositPerByte: Balance = 10_000_000;
+	pub const ItemMetadataDepositBase: Balance = 1_000_000_000;
+}
+
+/// Credit native protocol fees to the launchpad treasury. Fees that cannot endow the treasury
+/// on their own are burned.
+pub struct ToLaunchpadTreasury;
+impl OnUnbalanced<pallet_balances::NegativeImbalance<Runtime>> for ToLaunchpadTreasury {
+	fn on_nonzero_unbalanced(fee: pallet_balances::NegativeImbalance<Runtime>) {
+		Balances::resolve_creating(&LaunchpadTreasury::get(), fee);
+	}
+}
+
+/// Credit asset protocol fees to the launchpad treasury. Fees below the minimum balance of an
+/// asset the treasury holds none of are burned.
+pub struct AssetFeesToLaunchpadTreasury;
+impl OnUnbalanced<fungibles::CreditOf<AccountId, Assets>> for AssetFeesToLaunchpadTreasury {
+	fn on_nonzero_unbalanced(fee: fungibles::CreditOf<AccountId, Assets>) {
+		let _ = <Assets as fungibles::Balanced<AccountId>>::resolve(&LaunchpadTreasury::get(), fee);
+	}
 }
+
 /// Configure the pallet-collection in pallets/april-dao.
 impl pallet_collection::Config for Runtime {
 	type Event = Event;
 	type Currency = Balances;
 	type CollectionRandomness = RandomnessCollectiveFlip;
 	type SubmissionDeposit = SubmissionDeposit;
+	type ApproveOrigin = frame_system::EnsureRoot<AccountId>;
+	type Slash = ();
 	type MaxNFTOwned = MaxNFTOwned;
+	type Timestamp = Timestamp;
+	type MaxActiveSaleWindows = MaxActiveSaleWindows;
+	type UriLimit = UriLimit;
+	type NameLimit = NameLimit;
+	type DescriptionLimit = DescriptionLimit;
+	type AssetId = u128;
+	type Assets = Assets;
+	type MaxMintPerTx = MaxMintPerTx;
+	type KeyLimit = KeyLimit;
+	type ValueLimit = ValueLimit;
+	type MaxMilestones = MaxMilestones;
+	type ProtocolFee = ProtocolFee;
+	type FeeHandler = ToLaunchpadTreasury;
+	type AssetFeeHandler = AssetFeesToLaunchpadTreasury;
+	type AttributeDepositBase = AttributeDepositBase;
+	type DepositPerByte = DepositPerByte;
+	type MetadataDepositBase = ItemMetadataDepositBase;
+	type WeightInfo = pallet_collection::weights::SubstrateWeight<Runtime>;
 }
 
 parameter_types! {
@@ -479,11 +535,23 @@ impl_runtime_apis! {
 		}
 	}
 
-	impl pallet_collection_rpc_runtime_api::KittyApi<Block> for Runtime {
+	impl pallet_collection_rpc_runtime_api::KittyApi<Block, AccountId, Balance> for Runtime {
 		fn get_launchpad_collections() -> u32 {
 			CollectionModule::get_launchpad_collections()
 		}
 
+		fn royalty_info(
+			collection_id: pallet_collection::CollectionId,
+			nft_id: pallet_collection::NFTId,
+			sale_price: Balance,
+		) -> Option<(AccountId, Balance)> {
+			CollectionModule::royalty_info(collection_id, nft_id, sale_price)
+		}
+
+		fn collection_by_slug(slug: Vec<u8>) -> Option<pallet_collection::CollectionId> {
+			CollectionModule::collection_by_slug(slug)
+		}
+
 		// fn get_kitty_count() -> u64 {
 		// 	CollectionModule::kitty_cnt()
 		// }